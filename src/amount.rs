@@ -0,0 +1,522 @@
+use crate::MoneyError;
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::str::FromStr;
+
+/// Numeric backend used to store the amount of a `Money`.
+///
+/// All arithmetic, rounding, allocation and formatting performed by `Money`, `ExchangeRate`
+/// and `Formatter` is written against this trait, so the same API works regardless of how
+/// the amount is represented. `Decimal` is the default backend (`Money` is shorthand for
+/// `Money<Decimal>`), and `FixedPoint` provides scaled integer amounts.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_money::{Amount, FixedPoint, Money};
+/// use rusty_money::currencies::crypto::BTC;
+///
+/// let sats = Money::from_amount(FixedPoint::<8>::from_minor(150_000_000, 8), BTC);
+/// let halved: Money<FixedPoint<8>> = sats / 2;
+/// assert_eq!(halved.amount().to_string(), "0.75000000");
+/// ```
+pub trait Amount:
+    Clone
+    + fmt::Debug
+    + fmt::Display
+    + Eq
+    + Ord
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    /// Returns the additive identity.
+    fn zero() -> Self;
+
+    /// Returns the multiplicative identity.
+    fn one() -> Self;
+
+    /// Converts a `Decimal` into this backend.
+    ///
+    /// Backends with a fixed precision round the value to that precision.
+    fn from_decimal(value: Decimal) -> Self;
+
+    /// Parses a plain decimal string such as "-1234.5678".
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(MoneyError::InvalidAmountFormat)` or `Err(MoneyError::InvalidAmountDecimal)`
+    /// if the string is not a valid amount for this backend.
+    fn parse(s: &str) -> Result<Self, MoneyError>;
+
+    /// Rounds the amount to `digits` decimal places using the given strategy.
+    fn round_dp(&self, digits: u32, strategy: RoundingStrategy) -> Self;
+
+    /// Adds two amounts, returning `None` instead of panicking on overflow.
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+
+    /// Multiplies two amounts, returning `None` instead of panicking on overflow.
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+
+    /// Returns the amount as an `i128` if it is a whole number within range.
+    fn to_i128(&self) -> Option<i128>;
+
+    /// Converts the amount into a `Decimal`, rounding digits beyond its precision.
+    ///
+    /// Returns `None` if the integer part does not fit into a `Decimal`.
+    fn to_decimal(&self) -> Option<Decimal> {
+        self.to_string().parse().ok()
+    }
+
+    /// Multiplies the amount by a `Decimal` factor such as an exchange rate or a growth factor.
+    ///
+    /// Backends with a fixed precision override this so the factor is not rounded to their own
+    /// precision before multiplying.
+    fn mul_decimal(&self, factor: Decimal) -> Self {
+        self.clone() * Self::from_decimal(factor)
+    }
+
+    /// Divides the amount by a `Decimal` divisor.
+    ///
+    /// Backends with a fixed precision override this so the divisor is not rounded to their own
+    /// precision before dividing.
+    fn div_decimal(&self, divisor: Decimal) -> Self {
+        self.clone() / Self::from_decimal(divisor)
+    }
+
+    /// Creates an amount from an integer number of minor units, e.g. `(1050, 2)` is 10.50.
+    fn from_minor(minor: i64, exponent: u32) -> Self {
+        Self::from_decimal(Decimal::from(minor)) / Self::pow10(exponent)
+    }
+
+    /// Returns `10^exponent`.
+    fn pow10(exponent: u32) -> Self {
+        let ten = Self::from_decimal(Decimal::TEN);
+        (0..exponent).fold(Self::one(), |acc, _| acc * ten.clone())
+    }
+
+    /// Returns true if the amount is zero.
+    fn is_zero(&self) -> bool {
+        *self == Self::zero()
+    }
+
+    /// Returns true if the amount is strictly below zero.
+    fn is_negative(&self) -> bool {
+        *self < Self::zero()
+    }
+
+    /// Returns the absolute value of the amount.
+    fn abs(&self) -> Self {
+        if Amount::is_negative(self) {
+            -self.clone()
+        } else {
+            self.clone()
+        }
+    }
+}
+
+impl Amount for Decimal {
+    fn zero() -> Self {
+        Decimal::ZERO
+    }
+
+    fn one() -> Self {
+        Decimal::ONE
+    }
+
+    fn from_decimal(value: Decimal) -> Self {
+        value
+    }
+
+    fn to_decimal(&self) -> Option<Decimal> {
+        Some(*self)
+    }
+
+    fn parse(s: &str) -> Result<Self, MoneyError> {
+        // Leverages From<DecimalError> for MoneyError::InvalidAmountDecimal
+        Ok(Decimal::from_str(s)?)
+    }
+
+    fn round_dp(&self, digits: u32, strategy: RoundingStrategy) -> Self {
+        self.round_dp_with_strategy(digits, strategy)
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Decimal::checked_add(*self, *other)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Decimal::checked_mul(*self, *other)
+    }
+
+    fn to_i128(&self) -> Option<i128> {
+        // A 96-bit mantissa always fits; only whole numbers normalize to scale 0.
+        let normalized = self.normalize();
+        (normalized.scale() == 0).then(|| normalized.mantissa())
+    }
+
+    fn from_minor(minor: i64, exponent: u32) -> Self {
+        Decimal::new(minor, exponent)
+    }
+
+    fn is_zero(&self) -> bool {
+        Decimal::is_zero(self)
+    }
+
+    fn abs(&self) -> Self {
+        Decimal::abs(self)
+    }
+}
+
+/// A scaled integer amount with `SCALE` fixed decimal places.
+///
+/// The value is stored as an `i128` count of `10^-SCALE` units, so addition and subtraction
+/// are exact and never accumulate binary or decimal rounding drift. Multiplication and
+/// division truncate towards zero at `SCALE` decimal places, mirroring integer arithmetic.
+///
+/// # Panics
+///
+/// Arithmetic panics on `i128` overflow and on division by zero, like the primitive integers.
+/// `SCALE` must be at most 38.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FixedPoint<const SCALE: u32>(i128);
+
+impl<const SCALE: u32> FixedPoint<SCALE> {
+    /// `10^SCALE`, the number of units in one whole.
+    const FACTOR: i128 = 10i128.pow(SCALE);
+
+    /// Creates a value from a raw count of `10^-SCALE` units.
+    pub const fn from_units(units: i128) -> Self {
+        FixedPoint(units)
+    }
+
+    /// Returns the raw count of `10^-SCALE` units.
+    pub const fn units(&self) -> i128 {
+        self.0
+    }
+}
+
+/// Divides two integers, rounding the quotient with the given strategy.
+pub(crate) fn div_round_i128(n: i128, d: i128, strategy: RoundingStrategy) -> i128 {
+    let q = n / d;
+    let r = n % d;
+    if r == 0 {
+        return q;
+    }
+    let negative = (n < 0) != (d < 0);
+    let half = (r.unsigned_abs() * 2).cmp(&d.unsigned_abs());
+    if rounds_away(half, q % 2 != 0, negative, strategy) {
+        q + if negative { -1 } else { 1 }
+    } else {
+        q
+    }
+}
+
+/// Decides whether a truncated, inexact quotient should move one unit away from zero.
+///
+/// `half` compares the discarded remainder with half of the divisor, `odd` tells whether the
+/// truncated quotient is odd and `negative` gives the sign of the exact result.
+pub(crate) fn rounds_away(half: Ordering, odd: bool, negative: bool, strategy: RoundingStrategy) -> bool {
+    #[allow(deprecated)]
+    match strategy {
+        RoundingStrategy::MidpointNearestEven | RoundingStrategy::BankersRounding => {
+            half.is_gt() || (half.is_eq() && odd)
+        }
+        RoundingStrategy::MidpointAwayFromZero | RoundingStrategy::RoundHalfUp => !half.is_lt(),
+        RoundingStrategy::MidpointTowardZero | RoundingStrategy::RoundHalfDown => half.is_gt(),
+        RoundingStrategy::ToZero | RoundingStrategy::RoundDown => false,
+        RoundingStrategy::AwayFromZero | RoundingStrategy::RoundUp => true,
+        RoundingStrategy::ToNegativeInfinity => negative,
+        RoundingStrategy::ToPositiveInfinity => !negative,
+    }
+}
+
+/// Splits a plain decimal string into its sign, integer digits and fractional digits.
+pub(crate) fn split_decimal_str(s: &str) -> Result<(bool, &str, &str), MoneyError> {
+    let (negative, unsigned) = match s.as_bytes().first() {
+        Some(b'-') => (true, &s[1..]),
+        Some(b'+') => (false, &s[1..]),
+        _ => (false, s),
+    };
+    let (int_part, frac_part) = match unsigned.split_once('.') {
+        Some((i, f)) => (i, f),
+        None => (unsigned, ""),
+    };
+    let all_digits = |p: &str| p.bytes().all(|b| b.is_ascii_digit());
+    if (int_part.is_empty() && frac_part.is_empty()) || !all_digits(int_part) || !all_digits(frac_part) {
+        return Err(MoneyError::InvalidAmountFormat);
+    }
+    Ok((negative, int_part, frac_part))
+}
+
+impl<const SCALE: u32> Amount for FixedPoint<SCALE> {
+    fn zero() -> Self {
+        FixedPoint(0)
+    }
+
+    fn one() -> Self {
+        FixedPoint(Self::FACTOR)
+    }
+
+    fn from_decimal(value: Decimal) -> Self {
+        let rounded = value.round_dp_with_strategy(SCALE, RoundingStrategy::MidpointNearestEven);
+        let factor = 10i128.pow(SCALE - rounded.scale());
+        FixedPoint(rounded.mantissa().checked_mul(factor).expect("FixedPoint overflow"))
+    }
+
+    fn parse(s: &str) -> Result<Self, MoneyError> {
+        let (negative, int_part, frac_part) = split_decimal_str(s)?;
+        // Digits below the fixed precision are only accepted when they are zero.
+        let (kept, dropped) = frac_part.split_at(frac_part.len().min(SCALE as usize));
+        if dropped.bytes().any(|b| b != b'0') {
+            return Err(MoneyError::InvalidAmountFormat);
+        }
+        let digits = format!("{}{:0<width$}", int_part, kept, width = SCALE as usize);
+        let units = digits.parse::<i128>().map_err(|_| MoneyError::InvalidAmountFormat)?;
+        Ok(FixedPoint(if negative { -units } else { units }))
+    }
+
+    fn round_dp(&self, digits: u32, strategy: RoundingStrategy) -> Self {
+        if digits >= SCALE {
+            return *self;
+        }
+        let step = 10i128.pow(SCALE - digits);
+        FixedPoint(div_round_i128(self.0, step, strategy) * step)
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(FixedPoint)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        // a * b / f == (a / f) * b + (a % f) * b / f, which avoids overflowing on the
+        // intermediate product when the result itself fits.
+        let whole = (self.0 / Self::FACTOR).checked_mul(other.0)?;
+        let frac = (self.0 % Self::FACTOR).checked_mul(other.0)? / Self::FACTOR;
+        whole.checked_add(frac).map(FixedPoint)
+    }
+
+    fn to_i128(&self) -> Option<i128> {
+        (self.0 % Self::FACTOR == 0).then_some(self.0 / Self::FACTOR)
+    }
+
+    fn mul_decimal(&self, factor: Decimal) -> Self {
+        // units * mantissa / 10^scale, split like `checked_mul` and truncated toward zero like `Mul`.
+        let exact = |factor: Decimal| {
+            let divisor = 10i128.pow(factor.scale());
+            let whole = (self.0 / divisor).checked_mul(factor.mantissa())?;
+            let frac = (self.0 % divisor).checked_mul(factor.mantissa())? / divisor;
+            whole.checked_add(frac).map(FixedPoint)
+        };
+        // Very long factors can overflow the intermediate product, so retry with fewer digits
+        // before falling back to the factor at this backend's own precision.
+        exact(factor)
+            .or_else(|| exact(factor.round_dp(18)))
+            .unwrap_or_else(|| *self * Self::from_decimal(factor))
+    }
+
+    fn div_decimal(&self, divisor: Decimal) -> Self {
+        // units * 10^scale / mantissa, split like `Div` and truncated toward zero like it.
+        let exact = |divisor: Decimal| {
+            let factor = 10i128.pow(divisor.scale());
+            let whole = (self.0 / divisor.mantissa()).checked_mul(factor)?;
+            let frac = (self.0 % divisor.mantissa()).checked_mul(factor)? / divisor.mantissa();
+            whole.checked_add(frac).map(FixedPoint)
+        };
+        exact(divisor)
+            .or_else(|| exact(divisor.round_dp(18)))
+            .unwrap_or_else(|| *self / Self::from_decimal(divisor))
+    }
+
+    fn from_minor(minor: i64, exponent: u32) -> Self {
+        if exponent <= SCALE {
+            FixedPoint(i128::from(minor) * 10i128.pow(SCALE - exponent))
+        } else {
+            FixedPoint(i128::from(minor) / 10i128.pow(exponent - SCALE))
+        }
+    }
+
+    fn pow10(exponent: u32) -> Self {
+        FixedPoint(10i128.pow(exponent).checked_mul(Self::FACTOR).expect("FixedPoint overflow"))
+    }
+}
+
+impl<const SCALE: u32> Add for FixedPoint<SCALE> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        FixedPoint(self.0.checked_add(rhs.0).expect("FixedPoint addition overflowed"))
+    }
+}
+
+impl<const SCALE: u32> Sub for FixedPoint<SCALE> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        FixedPoint(self.0.checked_sub(rhs.0).expect("FixedPoint subtraction overflowed"))
+    }
+}
+
+impl<const SCALE: u32> Mul for FixedPoint<SCALE> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Amount::checked_mul(&self, &rhs).expect("FixedPoint multiplication overflowed")
+    }
+}
+
+impl<const SCALE: u32> Div for FixedPoint<SCALE> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        // a * f / b == (a / b) * f + (a % b) * f / b
+        let whole = (self.0 / rhs.0).checked_mul(Self::FACTOR);
+        let frac = (self.0 % rhs.0).checked_mul(Self::FACTOR).map(|p| p / rhs.0);
+        match (whole, frac) {
+            (Some(w), Some(f)) => FixedPoint(w.checked_add(f).expect("FixedPoint division overflowed")),
+            _ => panic!("FixedPoint division overflowed"),
+        }
+    }
+}
+
+impl<const SCALE: u32> Neg for FixedPoint<SCALE> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        FixedPoint(-self.0)
+    }
+}
+
+impl<const SCALE: u32> fmt::Display for FixedPoint<SCALE> {
+    /// Formats the value with exactly `SCALE` decimal places, e.g. "-12.50" for `FixedPoint<2>`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let magnitude = self.0.unsigned_abs();
+        let factor = Self::FACTOR.unsigned_abs();
+        if SCALE == 0 {
+            write!(f, "{}{}", sign, magnitude)
+        } else {
+            let (whole, frac) = (magnitude / factor, magnitude % factor);
+            write!(f, "{}{}.{:0width$}", sign, whole, frac, width = SCALE as usize)
+        }
+    }
+}
+
+impl<const SCALE: u32> FromStr for FixedPoint<SCALE> {
+    type Err = MoneyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        <Self as Amount>::parse(s)
+    }
+}
+
+impl<const SCALE: u32> Serialize for FixedPoint<SCALE> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        // Serialized as a string to avoid precision loss in formats like JSON.
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de, const SCALE: u32> Deserialize<'de> for FixedPoint<SCALE> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct FixedPointVisitor<const SCALE: u32>;
+
+        impl<const SCALE: u32> de::Visitor<'_> for FixedPointVisitor<SCALE> {
+            type Value = FixedPoint<SCALE>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a decimal amount as a string or integer")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                <FixedPoint<SCALE> as Amount>::parse(value).map_err(de::Error::custom)
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+                Ok(<FixedPoint<SCALE> as Amount>::from_decimal(Decimal::from(value)))
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+                Ok(<FixedPoint<SCALE> as Amount>::from_decimal(Decimal::from(value)))
+            }
+        }
+
+        // Accept both strings and integers, like rust_decimal's default serde support.
+        deserializer.deserialize_any(FixedPointVisitor::<SCALE>)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    type Cents = FixedPoint<2>;
+
+    #[test]
+    fn fixed_point_parses_and_displays() {
+        assert_eq!(Cents::parse("12.5").unwrap(), Cents::from_units(1250));
+        assert_eq!(Cents::parse("-0.07").unwrap(), Cents::from_units(-7));
+        assert_eq!(Cents::parse("3").unwrap().to_string(), "3.00");
+        assert_eq!(Cents::parse("-0.07").unwrap().to_string(), "-0.07");
+        assert_eq!(Cents::parse("1.2300").unwrap(), Cents::from_units(123));
+        assert_eq!(Cents::parse("1.234").unwrap_err(), MoneyError::InvalidAmountFormat);
+        assert_eq!(Cents::parse("1.2.3").unwrap_err(), MoneyError::InvalidAmountFormat);
+        assert_eq!(Cents::parse("").unwrap_err(), MoneyError::InvalidAmountFormat);
+        assert_eq!(FixedPoint::<0>::from_units(42).to_string(), "42");
+    }
+
+    #[test]
+    fn fixed_point_arithmetic() {
+        let a = Cents::from_units(1000); // 10.00
+        let b = Cents::from_units(300); // 3.00
+        assert_eq!(a + b, Cents::from_units(1300));
+        assert_eq!(a - b, Cents::from_units(700));
+        assert_eq!(a * b, Cents::from_units(3000));
+        assert_eq!(a / b, Cents::from_units(333)); // Truncated
+        assert_eq!(-a / b, Cents::from_units(-333));
+        assert_eq!(Cents::pow10(3), Cents::from_units(100_000));
+        assert_eq!(Cents::from_minor(1050, 2), Cents::from_units(1050));
+        assert_eq!(Cents::from_minor(12345, 3), Cents::from_units(1234));
+        assert_eq!(Cents::from_decimal(dec!(1.005)), Cents::from_units(100));
+
+        // The factor keeps its own precision instead of being rounded to cents first
+        assert_eq!(a.mul_decimal(dec!(1.0853)), Cents::from_units(1085));
+        assert_eq!((-a).mul_decimal(dec!(0.0049)), Cents::from_units(-4));
+        let large = Cents::from_units(i128::MAX / 10);
+        assert_eq!(large.mul_decimal(dec!(1.0000000000000000000000000001)), large);
+    }
+
+    #[test]
+    fn fixed_point_rounding() {
+        let value = Cents::from_units(1250); // 12.50
+        assert_eq!(value.round_dp(0, RoundingStrategy::MidpointNearestEven), Cents::from_units(1200));
+        assert_eq!(value.round_dp(0, RoundingStrategy::MidpointAwayFromZero), Cents::from_units(1300));
+        assert_eq!((-value).round_dp(0, RoundingStrategy::ToNegativeInfinity), Cents::from_units(-1300));
+        assert_eq!((-value).round_dp(0, RoundingStrategy::ToZero), Cents::from_units(-1200));
+        assert_eq!(value.round_dp(2, RoundingStrategy::ToZero), value);
+    }
+
+    #[test]
+    fn decimal_backend_round_trips() {
+        assert_eq!(<Decimal as Amount>::from_minor(1050, 2), dec!(10.50));
+        assert_eq!(<Decimal as Amount>::pow10(3), dec!(1000));
+        assert_eq!(<Decimal as Amount>::parse("1.5").unwrap(), dec!(1.5));
+        assert!(matches!(
+            <Decimal as Amount>::parse("abc").unwrap_err(),
+            MoneyError::InvalidAmountDecimal(_)
+        ));
+        assert_eq!(Amount::abs(&dec!(-2)), dec!(2));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::Currency;
+use crate::{Currency, Denomination};
 
 pub const BTC: Currency = Currency {
     exponent: 8,
@@ -96,3 +96,48 @@ pub const BSV: Currency = Currency {
     symbol: "BSV",
     symbol_first: false,
 };
+
+// Named sub-units, largest first. The currency's own code always names the major unit
+// and is resolved by `find_denomination`, so it is not repeated here.
+const BITCOIN_DENOMINATIONS: &[Denomination] = &[
+    Denomination { name: "mBTC", exponent: 3 },
+    Denomination { name: "bit", exponent: 6 },
+    Denomination { name: "satoshi", exponent: 8 },
+    Denomination { name: "sat", exponent: 8 },
+];
+// Bitcoin forks keep the satoshi but not the mBTC name.
+const SATOSHI_DENOMINATIONS: &[Denomination] = &[
+    Denomination { name: "satoshi", exponent: 8 },
+    Denomination { name: "sat", exponent: 8 },
+];
+const ETHEREUM_DENOMINATIONS: &[Denomination] = &[
+    Denomination { name: "ether", exponent: 0 },
+    Denomination { name: "finney", exponent: 3 },
+    Denomination { name: "szabo", exponent: 6 },
+    Denomination { name: "gwei", exponent: 9 },
+    Denomination { name: "mwei", exponent: 12 },
+    Denomination { name: "kwei", exponent: 15 },
+    Denomination { name: "wei", exponent: 18 },
+];
+// ERC-20 tokens with 18 decimals share Ether's unit names for their base units.
+const ERC20_DENOMINATIONS: &[Denomination] = &[
+    Denomination { name: "gwei", exponent: 9 },
+    Denomination { name: "wei", exponent: 18 },
+];
+const TEZOS_DENOMINATIONS: &[Denomination] = &[Denomination { name: "mutez", exponent: 6 }];
+const ZCASH_DENOMINATIONS: &[Denomination] = &[Denomination { name: "zatoshi", exponent: 8 }];
+
+/// Returns the named sub-units of a crypto currency, e.g. satoshi for BTC or gwei and wei for ETH.
+///
+/// Returns an empty slice for currencies without named sub-units.
+pub fn denominations(currency: &Currency) -> &'static [Denomination] {
+    match currency.code {
+        "BTC" => BITCOIN_DENOMINATIONS,
+        "BCH" | "BSV" => SATOSHI_DENOMINATIONS,
+        "ETH" => ETHEREUM_DENOMINATIONS,
+        "COMP" | "DAI" | "MKR" | "UNI" => ERC20_DENOMINATIONS,
+        "XTZ" => TEZOS_DENOMINATIONS,
+        "ZEC" => ZCASH_DENOMINATIONS,
+        _ => &[],
+    }
+}
@@ -0,0 +1,496 @@
+use crate::amount::{rounds_away, split_decimal_str};
+use crate::{Amount, MoneyError};
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::str::FromStr;
+
+pub use ethnum::U256;
+
+/// An arbitrary-precision decimal amount with a 256-bit magnitude.
+///
+/// `Decimal` holds only 28 significant digits, which is not enough for tokens with 18 decimal
+/// places such as ETH or DAI: a balance above roughly 10^10 tokens cannot be stored to the wei.
+/// `Decimal256` stores a sign, a `U256` magnitude and a decimal scale, so any on-chain `uint256`
+/// amount is represented exactly, whatever the token's number of decimals.
+///
+/// Addition and subtraction are exact. Multiplication is exact up to `Decimal256::MAX_SCALE`
+/// decimal places, and division is computed to `MAX_SCALE` decimal places with banker's
+/// rounding. Values compare, hash and test equal by numeric value, so `1.0 == 1.00`.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_money::{Decimal256, Money, U256};
+/// use rusty_money::currencies::crypto::ETH;
+///
+/// // 2^256 - 1 wei, the largest possible uint256 balance
+/// let balance = Money::from_amount(Decimal256::from_raw(U256::MAX, 18), ETH);
+/// assert_eq!(
+///     balance.amount().to_string(),
+///     "115792089237316195423570985008687907853269984665640564039457.584007913129639935"
+/// );
+/// ```
+///
+/// # Panics
+///
+/// Arithmetic panics if a magnitude exceeds `U256::MAX`, and on division by zero.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Decimal256 {
+    negative: bool,
+    magnitude: U256,
+    scale: u32,
+}
+
+impl Decimal256 {
+    /// The maximum number of decimal places kept by multiplication and division.
+    pub const MAX_SCALE: u32 = 36;
+
+    /// Creates a value from a raw on-chain integer and the token's number of decimals,
+    /// e.g. `from_raw(U256::new(1_500_000_000_000_000_000), 18)` is 1.5.
+    pub const fn from_raw(raw: U256, decimals: u32) -> Self {
+        Decimal256 {
+            negative: false,
+            magnitude: raw,
+            scale: decimals,
+        }
+    }
+
+    /// Returns the raw on-chain integer for a token with `decimals` decimal places.
+    ///
+    /// Returns `None` if the value is negative, has precision below `10^-decimals`, or does
+    /// not fit into a `U256`.
+    pub fn to_raw(&self, decimals: u32) -> Option<U256> {
+        if self.negative {
+            return None;
+        }
+        if decimals >= self.scale {
+            self.magnitude.checked_mul(pow10_u256(decimals - self.scale)?)
+        } else {
+            let (raw, rem) = self.magnitude.div_rem(pow10_u256(self.scale - decimals)?);
+            (rem == U256::ZERO).then_some(raw)
+        }
+    }
+
+    /// Returns the number of decimal places currently stored.
+    pub const fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    fn new(negative: bool, magnitude: U256, scale: u32) -> Self {
+        // Zero is never negative, so that equal values always share a representation sign.
+        Decimal256 {
+            negative: negative && magnitude != U256::ZERO,
+            magnitude,
+            scale,
+        }
+    }
+
+    /// Removes trailing fractional zeros, without going below `min_scale` decimal places.
+    fn normalized(&self, min_scale: u32) -> Self {
+        let ten = U256::new(10);
+        let mut result = *self;
+        while result.scale > min_scale {
+            let (q, r) = result.magnitude.div_rem(ten);
+            if r != U256::ZERO {
+                break;
+            }
+            result.magnitude = q;
+            result.scale -= 1;
+        }
+        result
+    }
+
+    /// Returns the magnitude expressed with `scale` decimal places, if it fits.
+    fn magnitude_at(&self, scale: u32) -> Option<U256> {
+        self.magnitude.checked_mul(pow10_u256(scale - self.scale)?)
+    }
+
+    /// Rounds the value to at most `digits` decimal places.
+    fn rounded(&self, digits: u32, strategy: RoundingStrategy) -> Self {
+        if self.scale <= digits {
+            return *self;
+        }
+        let divisor = pow10_u256(self.scale - digits).expect("Decimal256 scale overflowed");
+        let (q, r) = self.magnitude.div_rem(divisor);
+        let magnitude = round_quotient(q, r, divisor, self.negative, strategy);
+        Decimal256::new(self.negative, magnitude, digits)
+    }
+}
+
+/// Returns `10^exponent` as a `U256`, or `None` if it does not fit.
+fn pow10_u256(exponent: u32) -> Option<U256> {
+    U256::new(10).checked_pow(exponent)
+}
+
+/// Applies a rounding strategy to the truncated quotient `q` of a division with remainder `r`.
+fn round_quotient(q: U256, r: U256, divisor: U256, negative: bool, strategy: RoundingStrategy) -> U256 {
+    if r == U256::ZERO {
+        return q;
+    }
+    // Compare r with divisor - r rather than 2r with divisor, which could overflow.
+    let half = r.cmp(&(divisor - r));
+    if rounds_away(half, q % U256::new(2) != U256::ZERO, negative, strategy) {
+        q.checked_add(U256::ONE).expect("Decimal256 overflow")
+    } else {
+        q
+    }
+}
+
+impl Amount for Decimal256 {
+    fn zero() -> Self {
+        Decimal256::default()
+    }
+
+    fn one() -> Self {
+        Decimal256::new(false, U256::ONE, 0)
+    }
+
+    fn from_decimal(value: Decimal) -> Self {
+        let mantissa = value.mantissa();
+        Decimal256::new(mantissa < 0, U256::new(mantissa.unsigned_abs()), value.scale())
+    }
+
+    fn parse(s: &str) -> Result<Self, MoneyError> {
+        let (negative, int_part, frac_part) = split_decimal_str(s)?;
+        let digits = format!("{}{}", int_part, frac_part);
+        let magnitude = U256::from_str_radix(&digits, 10).map_err(|_| MoneyError::InvalidAmountFormat)?;
+        let value = Decimal256::new(negative, magnitude, frac_part.len() as u32);
+        Ok(value.rounded(Decimal256::MAX_SCALE, RoundingStrategy::MidpointNearestEven))
+    }
+
+    fn round_dp(&self, digits: u32, strategy: RoundingStrategy) -> Self {
+        self.rounded(digits, strategy)
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        let scale = self.scale.max(other.scale);
+        let lhs_mag = self.magnitude_at(scale)?;
+        let rhs_mag = other.magnitude_at(scale)?;
+        if self.negative == other.negative {
+            Some(Decimal256::new(self.negative, lhs_mag.checked_add(rhs_mag)?, scale))
+        } else if lhs_mag >= rhs_mag {
+            Some(Decimal256::new(self.negative, lhs_mag - rhs_mag, scale))
+        } else {
+            Some(Decimal256::new(other.negative, rhs_mag - lhs_mag, scale))
+        }
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        let negative = self.negative != other.negative;
+        let product = |a: &Decimal256, b: &Decimal256| {
+            a.magnitude
+                .checked_mul(b.magnitude)
+                .map(|magnitude| Decimal256::new(negative, magnitude, a.scale + b.scale))
+        };
+        // Retry without trailing zeros before giving up, e.g. 1.000...0 * large values.
+        let result = product(self, other).or_else(|| product(&self.normalized(0), &other.normalized(0)))?;
+        Some(result.rounded(Decimal256::MAX_SCALE, RoundingStrategy::MidpointNearestEven))
+    }
+
+    fn to_i128(&self) -> Option<i128> {
+        let whole = self.normalized(0);
+        if whole.scale != 0 || whole.magnitude > U256::new(1 << 127) {
+            return None;
+        }
+        let magnitude = whole.magnitude.as_u128();
+        if whole.negative {
+            0i128.checked_sub_unsigned(magnitude)
+        } else {
+            i128::try_from(magnitude).ok()
+        }
+    }
+
+    fn from_minor(minor: i64, exponent: u32) -> Self {
+        Decimal256::new(minor < 0, U256::new(u128::from(minor.unsigned_abs())), exponent)
+    }
+
+    fn pow10(exponent: u32) -> Self {
+        let magnitude = pow10_u256(exponent).expect("Decimal256 overflow");
+        Decimal256::new(false, magnitude, 0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.magnitude == U256::ZERO
+    }
+
+    fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    fn abs(&self) -> Self {
+        Decimal256::new(false, self.magnitude, self.scale)
+    }
+}
+
+impl Add for Decimal256 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Amount::checked_add(&self, &rhs).expect("Decimal256 addition overflowed")
+    }
+}
+
+impl Sub for Decimal256 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + (-rhs)
+    }
+}
+
+impl Mul for Decimal256 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Amount::checked_mul(&self, &rhs).expect("Decimal256 multiplication overflowed")
+    }
+}
+
+impl Div for Decimal256 {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        if rhs.magnitude == U256::ZERO {
+            panic!("Division by zero");
+        }
+        let negative = self.negative != rhs.negative;
+        // a / b == (ma / mb) * 10^(sb - sa), so the quotient of the magnitudes needs
+        // MAX_SCALE + sb - sa fractional digits to give MAX_SCALE decimal places.
+        let target = Decimal256::MAX_SCALE.max(self.scale);
+        let digits = target + rhs.scale - self.scale;
+        let (mut q, mut r) = self.magnitude.div_rem(rhs.magnitude);
+        let ten = U256::new(10);
+        let mut produced = 0;
+        // Long division, one decimal digit at a time, until the precision is reached or
+        // the quotient would no longer fit.
+        while produced < digits && r != U256::ZERO {
+            let (Some(next_q), Some(next_r)) = (q.checked_mul(ten), r.checked_mul(ten)) else {
+                break;
+            };
+            let (digit, rem) = next_r.div_rem(rhs.magnitude);
+            q = next_q + digit;
+            r = rem;
+            produced += 1;
+        }
+        let magnitude = round_quotient(q, r, rhs.magnitude, negative, RoundingStrategy::MidpointNearestEven);
+        // Exact divisions still have `digits - produced` implicit trailing zeros.
+        let exact_scale = self.scale + produced;
+        let result = if exact_scale >= rhs.scale {
+            Decimal256::new(negative, magnitude, exact_scale - rhs.scale)
+        } else {
+            let magnitude = magnitude
+                .checked_mul(pow10_u256(rhs.scale - exact_scale).expect("Decimal256 overflow"))
+                .expect("Decimal256 division overflowed");
+            Decimal256::new(negative, magnitude, 0)
+        };
+        result.normalized(self.scale)
+    }
+}
+
+impl Neg for Decimal256 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Decimal256::new(!self.negative, self.magnitude, self.scale)
+    }
+}
+
+impl PartialEq for Decimal256 {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal256 {}
+
+impl PartialOrd for Decimal256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => return Ordering::Greater,
+            (true, false) => return Ordering::Less,
+            _ => {}
+        }
+        let scale = self.scale.max(other.scale);
+        // A magnitude that overflows when rescaled is larger than any that does not.
+        let magnitudes = match (self.magnitude_at(scale), other.magnitude_at(scale)) {
+            (Some(lhs), Some(rhs)) => lhs.cmp(&rhs),
+            (None, Some(_)) => Ordering::Greater,
+            (Some(_), None) => Ordering::Less,
+            (None, None) => unreachable!("one of the scales is the common scale"),
+        };
+        if self.negative {
+            magnitudes.reverse()
+        } else {
+            magnitudes
+        }
+    }
+}
+
+impl Hash for Decimal256 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Hash the canonical form so that equal values hash alike regardless of scale.
+        let canonical = self.normalized(0);
+        canonical.negative.hash(state);
+        canonical.magnitude.hash(state);
+        canonical.scale.hash(state);
+    }
+}
+
+impl fmt::Display for Decimal256 {
+    /// Formats the value as a plain decimal string keeping its scale, e.g. "-0.0100".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.negative { "-" } else { "" };
+        let digits = self.magnitude.to_string();
+        let scale = self.scale as usize;
+        if scale == 0 {
+            return write!(f, "{}{}", sign, digits);
+        }
+        let digits = format!("{:0>width$}", digits, width = scale + 1);
+        let (int_part, frac_part) = digits.split_at(digits.len() - scale);
+        write!(f, "{}{}.{}", sign, int_part, frac_part)
+    }
+}
+
+impl FromStr for Decimal256 {
+    type Err = MoneyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        <Self as Amount>::parse(s)
+    }
+}
+
+impl Serialize for Decimal256 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        // Serialized as a string, since few formats can hold 256-bit numbers.
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Decimal256 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct Decimal256Visitor;
+
+        impl de::Visitor<'_> for Decimal256Visitor {
+            type Value = Decimal256;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a decimal amount as a string or integer")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                <Decimal256 as Amount>::parse(value).map_err(de::Error::custom)
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+                Ok(<Decimal256 as Amount>::from_minor(value, 0))
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+                Ok(Decimal256::from_raw(U256::new(u128::from(value)), 0))
+            }
+        }
+
+        deserializer.deserialize_any(Decimal256Visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::currencies::crypto::ETH;
+    use crate::Money;
+    use rust_decimal_macros::dec;
+
+    fn d(s: &str) -> Decimal256 {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn decimal256_parses_and_displays() {
+        assert_eq!(d("1.50").to_string(), "1.50");
+        assert_eq!(d("-0.01").to_string(), "-0.01");
+        assert_eq!(d("-0").to_string(), "0");
+        assert_eq!(d("+7").to_string(), "7");
+        assert_eq!(d(".5").to_string(), "0.5");
+        assert_eq!("1,5".parse::<Decimal256>().unwrap_err(), MoneyError::InvalidAmountFormat);
+        assert_eq!("".parse::<Decimal256>().unwrap_err(), MoneyError::InvalidAmountFormat);
+        assert_eq!(Decimal256::from_decimal(dec!(-12.345)).to_string(), "-12.345");
+    }
+
+    #[test]
+    fn decimal256_equality_ignores_scale() {
+        assert_eq!(d("1.0"), d("1.000"));
+        assert!(d("-2") < d("-1.5"));
+        assert!(d("0.1") > d("0.09"));
+        assert_eq!(d("0"), -d("0"));
+    }
+
+    #[test]
+    fn decimal256_arithmetic() {
+        assert_eq!(d("1.25") + d("2.5"), d("3.75"));
+        assert_eq!(d("1.25") - d("2.5"), d("-1.25"));
+        assert_eq!(d("-1.5") * d("2.5"), d("-3.75"));
+        assert_eq!(d("10") / d("4"), d("2.5"));
+        assert_eq!((d("10.00") / d("2")).to_string(), "5.00");
+        assert_eq!(d("1") / d("3"), d("0.333333333333333333333333333333333333"));
+        assert_eq!(d("2") / d("3"), d("0.666666666666666666666666666666666667"));
+    }
+
+    #[test]
+    fn decimal256_holds_large_token_balances_in_wei() {
+        // 10^12 ETH plus one wei does not fit in a Decimal's 28 significant digits.
+        let balance = Money::from_amount(d("1000000000000.000000000000000001"), ETH);
+        let doubled = (balance.clone() + balance.clone()).unwrap();
+        assert_eq!(doubled.amount().to_string(), "2000000000000.000000000000000002");
+
+        // Multiplying two 18 decimal values keeps every digit.
+        let product = *balance.amount() * *balance.amount();
+        assert_eq!(product.to_string(), "1000000000000000000000000.000002000000000000000000000000000001");
+
+        let raw = balance.amount().to_raw(18).unwrap();
+        assert_eq!(raw.to_string(), "1000000000000000000000000000001");
+        assert_eq!(Decimal256::from_raw(raw, 18), *balance.amount());
+        assert_eq!(balance.amount().to_raw(17), None);
+        assert_eq!((-*balance.amount()).to_raw(18), None);
+    }
+
+    #[test]
+    fn decimal256_rounding() {
+        let value = d("2.345");
+        assert_eq!(value.round_dp(2, RoundingStrategy::MidpointNearestEven), d("2.34"));
+        assert_eq!(value.round_dp(2, RoundingStrategy::MidpointAwayFromZero), d("2.35"));
+        assert_eq!((-value).round_dp(0, RoundingStrategy::ToNegativeInfinity), d("-3"));
+        assert_eq!(value.round_dp(5, RoundingStrategy::ToZero).to_string(), "2.345");
+    }
+
+    #[test]
+    fn decimal256_money_allocation_and_serde() {
+        let money = Money::from_amount(Decimal256::from_raw(U256::new(10), 18), ETH);
+        let shares = money.allocate_to(3).unwrap();
+        let raws: Vec<U256> = shares.iter().map(|m| m.amount().to_raw(18).unwrap()).collect();
+        assert_eq!(raws, vec![U256::new(4), U256::new(3), U256::new(3)]);
+
+        let serialized = serde_json::to_string(&Decimal256::from_raw(U256::MAX, 0)).unwrap();
+        assert_eq!(serialized, format!("\"{}\"", U256::MAX));
+        let deserialized: Decimal256 = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, Decimal256::from_raw(U256::MAX, 0));
+        let deserialized: Decimal256 = serde_json::from_str("-42").unwrap();
+        assert_eq!(deserialized, d("-42"));
+    }
+}
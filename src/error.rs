@@ -21,6 +21,45 @@ pub enum MoneyError {
     EmptySum,
     /// Locale formatting string was invalid (indicates internal library error).
     InvalidLocaleFormat,
+    /// Provided denomination name is not known for the currency.
+    InvalidDenomination,
+    /// Amount cannot be converted without losing precision (e.g., sub-minor-unit digits).
+    PrecisionLoss,
+    /// Amount is outside the range of the requested type.
+    Overflow,
+    /// A floating point amount was NaN or infinite.
+    NonFiniteAmount,
+    /// Requested percentile was outside of the range 0 to 100.
+    InvalidPercentile,
+    /// No exchange rate was available for the requested currency pair.
+    MissingExchangeRate,
+    /// A date range ended before it started.
+    InvalidDateRange,
+    /// A schedule or annuity was requested over zero periods.
+    InvalidPeriods,
+    /// No rate solving the given cash flows was found.
+    NoSolution,
+    /// Fee tiers were empty, not ascending or did not end with an unbounded tier, or a fee minimum was above the
+    /// maximum.
+    InvalidFeeSchedule,
+    /// An index series held an invalid date or a value that was not positive.
+    InvalidIndexSeries,
+    /// An index series had no value for the requested date.
+    MissingIndexValue,
+    /// No historical exchange rate covered the requested date.
+    MissingHistoricalRate,
+    /// A quote had a bid that was not positive, an ask below the bid, or a markup outside of 0 to 1.
+    InvalidQuote,
+    /// A rate provider could not be queried. Holds a description of the failure.
+    RateProviderFailed(String),
+    /// A currency code was not recognized. Holds the code.
+    UnknownCurrency(String),
+    /// A rate file could not be parsed. Holds a description of the problem.
+    InvalidRateFile(String),
+    /// An exchange rate was zero, negative or not finite.
+    InvalidExchangeRate,
+    /// The spread of an exchange rate was negative.
+    InvalidSpread,
 }
 
 // Manual PartialEq implementation because ParseIntError doesn't derive it
@@ -35,6 +74,25 @@ impl PartialEq for MoneyError {
             (Self::InvalidRatio, Self::InvalidRatio) => true,
             (Self::EmptySum, Self::EmptySum) => true,
             (Self::InvalidLocaleFormat, Self::InvalidLocaleFormat) => true,
+            (Self::InvalidDenomination, Self::InvalidDenomination) => true,
+            (Self::PrecisionLoss, Self::PrecisionLoss) => true,
+            (Self::Overflow, Self::Overflow) => true,
+            (Self::NonFiniteAmount, Self::NonFiniteAmount) => true,
+            (Self::InvalidPercentile, Self::InvalidPercentile) => true,
+            (Self::MissingExchangeRate, Self::MissingExchangeRate) => true,
+            (Self::InvalidDateRange, Self::InvalidDateRange) => true,
+            (Self::InvalidPeriods, Self::InvalidPeriods) => true,
+            (Self::NoSolution, Self::NoSolution) => true,
+            (Self::InvalidFeeSchedule, Self::InvalidFeeSchedule) => true,
+            (Self::InvalidIndexSeries, Self::InvalidIndexSeries) => true,
+            (Self::MissingIndexValue, Self::MissingIndexValue) => true,
+            (Self::MissingHistoricalRate, Self::MissingHistoricalRate) => true,
+            (Self::InvalidQuote, Self::InvalidQuote) => true,
+            (Self::RateProviderFailed(a), Self::RateProviderFailed(b)) => a == b,
+            (Self::UnknownCurrency(a), Self::UnknownCurrency(b)) => a == b,
+            (Self::InvalidRateFile(a), Self::InvalidRateFile(b)) => a == b,
+            (Self::InvalidExchangeRate, Self::InvalidExchangeRate) => true,
+            (Self::InvalidSpread, Self::InvalidSpread) => true,
             _ => false,
         }
     }
@@ -52,6 +110,27 @@ impl fmt::Display for MoneyError {
             MoneyError::InvalidRatio => write!(f, "Provided allocation ratios were invalid"),
             MoneyError::EmptySum => write!(f, "Cannot sum an empty iterator of Money"),
             MoneyError::InvalidLocaleFormat => write!(f, "Locale formatting string was invalid"),
+            MoneyError::InvalidDenomination => write!(f, "Provided denomination is not known for the currency"),
+            MoneyError::PrecisionLoss => write!(f, "Amount cannot be converted without losing precision"),
+            MoneyError::Overflow => write!(f, "Amount is outside the range of the requested type"),
+            MoneyError::NonFiniteAmount => write!(f, "Floating point amount is NaN or infinite"),
+            MoneyError::InvalidPercentile => write!(f, "Percentile must be between 0 and 100"),
+            MoneyError::MissingExchangeRate => write!(f, "No exchange rate available for currency pair"),
+            MoneyError::InvalidDateRange => write!(f, "End date is before start date"),
+            MoneyError::InvalidPeriods => write!(f, "Number of periods must be at least one"),
+            MoneyError::NoSolution => write!(f, "No rate solving the cash flows was found"),
+            MoneyError::InvalidFeeSchedule => {
+                write!(f, "Fee tiers must ascend to an unbounded tier and the minimum must not exceed the maximum")
+            }
+            MoneyError::InvalidIndexSeries => write!(f, "Index series must hold dates with positive values"),
+            MoneyError::MissingIndexValue => write!(f, "No index value available for date"),
+            MoneyError::MissingHistoricalRate => write!(f, "No exchange rate covers the requested date"),
+            MoneyError::InvalidQuote => write!(f, "Quote prices must be positive with the ask at or above the bid"),
+            MoneyError::RateProviderFailed(reason) => write!(f, "Rate provider failed: {}", reason),
+            MoneyError::UnknownCurrency(code) => write!(f, "Unknown currency code: {}", code),
+            MoneyError::InvalidRateFile(reason) => write!(f, "Invalid rate file: {}", reason),
+            MoneyError::InvalidExchangeRate => write!(f, "Exchange rates must be positive and finite"),
+            MoneyError::InvalidSpread => write!(f, "Spreads must not be negative"),
         }
     }
 }
@@ -1,41 +1,904 @@
-use crate::{Currency, Money, MoneyError};
-use rust_decimal::Decimal;
-use std::collections::HashMap;
+use crate::{
+    find_currency, Amount, Currency, FxQuote, ImportedRate, Interpolation, Money, MoneyError, NaiveDate, RateFormat,
+    RateProvider, RoundingPolicy, UnknownCurrencies,
+};
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, OnceLock};
 
-/// Stores `ExchangeRate`s for easier access.
-#[derive(Debug, Default)]
+/// Stores `ExchangeRate`s as a graph of currencies and finds rates between them.
+///
+/// When a pair was not stored, the exchange derives it, as allowed by its `RatePolicy`: by inverting the stored
+/// rate of the reverse pair, or by triangulating through pivot currencies.
+///
+/// Besides the current rates, an exchange can hold the history of rates by effective date, for conversions as of
+/// a past date. Rates that can be neither found nor derived are asked from its `RateProvider`, if it has one.
+///
+/// An exchange serializes as a table of its current and historical rates, sorted by pair and date, with currencies
+/// written as codes. Its policy and provider are configuration rather than data, and are not serialized.
+#[derive(Debug, Clone, Default)]
 pub struct Exchange {
-    map: HashMap<String, ExchangeRate>,
+    rates: HashMap<Currency, HashMap<Currency, Edge>>,
+    history: HashMap<(Currency, Currency), BTreeMap<NaiveDate, DatedRate>>,
+    policy: RatePolicy,
+    provider: Option<Arc<dyn RateProvider>>,
+    /// The stored rates and their inverses by the currency they convert from, built on the first search.
+    graph: OnceLock<HashMap<Currency, Vec<Leg>>>,
+}
+
+/// A historical rate, effective from its date until the next one or until `until`.
+#[derive(Debug, Clone)]
+struct DatedRate {
+    rate: ExchangeRate,
+    until: Option<NaiveDate>,
+}
+
+/// A stored rate, with the relative cost of converting at it if known.
+#[derive(Debug, Clone)]
+struct Edge {
+    rate: ExchangeRate,
+    spread: Option<Decimal>,
+}
+
+/// A step of a conversion path, stored or inverted.
+#[derive(Debug, Clone)]
+struct Leg {
+    rate: ExchangeRate,
+    spread: Decimal,
+    inverted: bool,
 }
 
 impl Exchange {
     pub fn new() -> Exchange {
         Exchange {
-            map: HashMap::new(),
+            rates: HashMap::new(),
+            history: HashMap::new(),
+            policy: RatePolicy::default(),
+            provider: None,
+            graph: OnceLock::new(),
         }
     }
 
+    /// Returns the exchange with a provider to query for rates it cannot find or derive.
+    ///
+    /// Wrap the provider in `CachedRates` to remember the rates it returns.
+    pub fn with_provider(mut self, provider: impl RateProvider + 'static) -> Exchange {
+        self.provider = Some(Arc::new(provider));
+        self
+    }
+
+    /// Replaces the provider queried for rates the exchange cannot find or derive.
+    pub fn set_provider(&mut self, provider: impl RateProvider + 'static) {
+        self.provider = Some(Arc::new(provider));
+    }
+
+    /// Returns the exchange with the given policy for derived rates.
+    pub fn with_policy(mut self, policy: RatePolicy) -> Exchange {
+        self.policy = policy;
+        self
+    }
+
+    /// Returns the policy used for derived rates.
+    pub fn policy(&self) -> &RatePolicy {
+        &self.policy
+    }
+
+    /// Replaces the policy used for derived rates.
+    pub fn set_policy(&mut self, policy: RatePolicy) {
+        self.policy = policy;
+    }
+
     /// Update an ExchangeRate or add it if does not exist.
     pub fn set_rate(&mut self, rate: &ExchangeRate) {
-        let key = Exchange::generate_key(&rate.from, &rate.to);
-        // Clone the rate since ExchangeRate is not Copy
-        self.map.insert(key, rate.clone());
+        self.insert(rate, None);
     }
 
-    /// Return the ExchangeRate given the currency pair.
+    /// Updates or adds an ExchangeRate together with its spread, the relative cost of converting at it.
+    ///
+    /// A spread of `0.002` means that 0.2% of the value is lost to the spread. Spreads decide which path is
+    /// cheapest when a rate is triangulated; rates stored without a spread count as free.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::InvalidSpread` if `spread` is negative.
+    pub fn set_rate_with_spread(&mut self, rate: &ExchangeRate, spread: Decimal) -> Result<(), MoneyError> {
+        if spread < Decimal::ZERO {
+            return Err(MoneyError::InvalidSpread);
+        }
+        self.insert(rate, Some(spread));
+        Ok(())
+    }
+
+    /// Updates or adds the mid rate of a two-sided quote, with the spread of the quote.
+    pub fn set_quote<A: Amount>(&mut self, quote: &FxQuote<A>) {
+        self.insert(&quote.mid_rate(), Some(quote.relative_spread()));
+    }
+
+    fn insert(&mut self, rate: &ExchangeRate, spread: Option<Decimal>) {
+        let edge = Edge {
+            rate: rate.clone(),
+            spread,
+        };
+        self.rates.entry(rate.from).or_default().insert(rate.to, edge);
+        self.graph.take();
+    }
+
+    /// Return the ExchangeRate given the currency pair, deriving it if the policy of the exchange allows.
     pub fn get_rate(&self, from: &Currency, to: &Currency) -> Option<ExchangeRate> {
-        let key = Exchange::generate_key(from, to);
-        // Use cloned() instead of copied() as ExchangeRate is not Copy
-        self.map.get(&key).cloned()
+        self.lookup(from, to).map(|lookup| lookup.rate)
+    }
+
+    /// Returns the rate for a currency pair and how it was found, using the policy of the exchange.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::{Exchange, ExchangeRate, RateSource};
+    /// use rusty_money::currencies::iso::{EUR, USD};
+    /// use rust_decimal_macros::dec;
+    ///
+    /// let mut exchange = Exchange::new();
+    /// exchange.set_rate(&ExchangeRate::new(EUR, USD, dec!(1.25)).unwrap());
+    ///
+    /// let lookup = exchange.lookup(&USD, &EUR).unwrap();
+    /// assert_eq!(lookup.source, RateSource::Inverted);
+    /// assert_eq!(lookup.rate, ExchangeRate::new(USD, EUR, dec!(0.8)).unwrap());
+    /// ```
+    pub fn lookup(&self, from: &Currency, to: &Currency) -> Option<RateLookup> {
+        self.lookup_with(from, to, &self.policy)
+    }
+
+    /// Returns the rate for a currency pair and how it was found, using the given policy.
+    ///
+    /// If the policy allows derived rates, the exchange searches for paths of at most `max_hops` stored or inverted
+    /// rates. The path with the lowest total spread wins, then the one with the fewest hops, one through the
+    /// preferred pivot, and the one with the fewest inverted rates. So a stored rate for the pair is used unless
+    /// spreads make another path cheaper. Otherwise only a stored rate for the pair is returned.
+    ///
+    /// If no rate is found, the provider of the exchange is asked for the pair, and for the reverse pair to invert
+    /// if the policy allows derived rates. Provider failures are treated as a missing rate; use `try_lookup` to
+    /// see them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::{Exchange, ExchangeRate, RatePolicy, RateSource};
+    /// use rusty_money::currencies::iso::{EUR, GBP, JPY};
+    /// use rust_decimal::RoundingStrategy;
+    /// use rust_decimal_macros::dec;
+    ///
+    /// // A feed that only publishes rates against EUR
+    /// let mut exchange = Exchange::new();
+    /// exchange.set_rate(&ExchangeRate::new(EUR, GBP, dec!(0.85)).unwrap());
+    /// exchange.set_rate(&ExchangeRate::new(EUR, JPY, dec!(160)).unwrap());
+    ///
+    /// let four_places = RatePolicy::new().with_precision(4, RoundingStrategy::MidpointNearestEven);
+    /// let lookup = exchange.lookup_with(&GBP, &JPY, &four_places).unwrap();
+    /// assert_eq!(lookup.source, RateSource::Triangulated);
+    /// assert_eq!(lookup.rate, ExchangeRate::new(GBP, JPY, dec!(188.2353)).unwrap());
+    ///
+    /// assert_eq!(exchange.lookup_with(&GBP, &JPY, &RatePolicy::stored_only()), None);
+    /// ```
+    pub fn lookup_with(&self, from: &Currency, to: &Currency, policy: &RatePolicy) -> Option<RateLookup> {
+        self.find(from, to, policy).or_else(|| self.provide(from, to, policy).ok().flatten())
+    }
+
+    /// Returns the rate for a currency pair and how it was found, using the policy of the exchange.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the provider if it had to be asked and failed, and `MoneyError::MissingExchangeRate`
+    /// if no rate was found.
+    pub fn try_lookup(&self, from: &Currency, to: &Currency) -> Result<RateLookup, MoneyError> {
+        match self.find(from, to, &self.policy) {
+            Some(lookup) => Ok(lookup),
+            None => self.provide(from, to, &self.policy)?.ok_or(MoneyError::MissingExchangeRate),
+        }
+    }
+
+    /// Asks the provider for a rate, inverting the reverse pair if the policy allows.
+    fn provide(&self, from: &Currency, to: &Currency, policy: &RatePolicy) -> Result<Option<RateLookup>, MoneyError> {
+        let Some(provider) = &self.provider else {
+            return Ok(None);
+        };
+        if from == to {
+            return Ok(None);
+        }
+        if let Some(rate) = provider.rate(from, to)? {
+            return Ok(Some(RateLookup {
+                rate: rate.clone(),
+                source: RateSource::Provided,
+                legs: vec![rate],
+            }));
+        }
+        if !policy.allow_derived {
+            return Ok(None);
+        }
+        let Some(reverse) = provider.rate(to, from)? else {
+            return Ok(None);
+        };
+        let leg = reverse.inverse();
+        let rate = policy.round(leg.rate);
+        if rate <= Decimal::ZERO {
+            return Ok(None);
+        }
+        Ok(Some(RateLookup {
+            rate: ExchangeRate { rate, ..leg.clone() },
+            source: RateSource::Inverted,
+            legs: vec![leg],
+        }))
+    }
+
+    /// Finds a stored or derived rate without asking the provider.
+    fn find(&self, from: &Currency, to: &Currency, policy: &RatePolicy) -> Option<RateLookup> {
+        let edge = self.rates.get(from).and_then(|edges| edges.get(to));
+        // A stored rate without a spread is the cheapest and shortest path there is
+        let free = edge.is_some_and(|edge| edge.spread.unwrap_or(Decimal::ZERO).is_zero());
+        if free || !policy.allow_derived || policy.max_hops == 0 {
+            let edge = edge?;
+            return Some(RateLookup {
+                rate: edge.rate.clone(),
+                source: RateSource::Stored,
+                legs: vec![edge.rate.clone()],
+            });
+        }
+
+        search(self.legs(), from, to, policy)
+    }
+
+    /// Adds a historical rate that takes effect on `date` and stays in effect until the next rate for the pair.
+    pub fn set_rate_at(&mut self, rate: &ExchangeRate, date: NaiveDate) {
+        self.insert_dated(rate, date, None);
+    }
+
+    /// Adds a historical rate that is in effect from `start` to `end`, both inclusive.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::InvalidDateRange` if `end` is before `start`.
+    pub fn set_rate_between(
+        &mut self,
+        rate: &ExchangeRate,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<(), MoneyError> {
+        if end < start {
+            return Err(MoneyError::InvalidDateRange);
+        }
+        self.insert_dated(rate, start, Some(end));
+        Ok(())
+    }
+
+    /// Adds many historical rates at once, each taking effect on its date.
+    pub fn load_history<I>(&mut self, rates: I)
+    where
+        I: IntoIterator<Item = (NaiveDate, ExchangeRate)>,
+    {
+        for (date, rate) in rates {
+            self.insert_dated(&rate, date, None);
+        }
+    }
+
+    /// Imports the rates in a file into the history of the exchange, and returns how many were imported.
+    ///
+    /// The rates of the latest date in the file also become the current rates of their pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::{Exchange, ExchangeRate, RateFormat, UnknownCurrencies};
+    /// use rusty_money::currencies::iso::{EUR, USD};
+    /// use rust_decimal_macros::dec;
+    ///
+    /// let xml = "<Cube><Cube time='2024-06-14'><Cube currency='USD' rate='1.0686'/></Cube></Cube>";
+    /// let mut exchange = Exchange::new();
+    /// exchange.import(RateFormat::EcbXml, xml, UnknownCurrencies::Fail).unwrap();
+    /// assert_eq!(exchange.get_rate(&EUR, &USD), Some(ExchangeRate::new(EUR, USD, dec!(1.0686)).unwrap()));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of `RateFormat::parse`, in which case nothing is imported.
+    pub fn import(
+        &mut self,
+        format: RateFormat,
+        contents: &str,
+        unknown: UnknownCurrencies,
+    ) -> Result<usize, MoneyError> {
+        let imported = format.parse(contents, unknown)?;
+        let mut latest: HashMap<(Currency, Currency), &ImportedRate> = HashMap::new();
+        for rate in &imported {
+            let current = latest.entry((rate.rate.from, rate.rate.to)).or_insert(rate);
+            if rate.date > current.date {
+                *current = rate;
+            }
+        }
+        for rate in latest.values() {
+            self.set_rate(&rate.rate);
+        }
+        self.load_history(imported.iter().map(|rate| (rate.date, rate.rate.clone())));
+        Ok(imported.len())
     }
 
-    fn generate_key(from: &Currency, to: &Currency) -> String {
-        from.to_string() + "-" + &to.to_string()
+    /// Writes the current and historical rates of the exchange as JSON, to be reloaded with `from_json`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::{Exchange, ExchangeRate};
+    /// use rusty_money::currencies::iso::{EUR, USD};
+    /// use rust_decimal_macros::dec;
+    ///
+    /// let mut exchange = Exchange::new();
+    /// exchange.set_rate(&ExchangeRate::new(EUR, USD, dec!(1.0850)).unwrap());
+    ///
+    /// let json = exchange.to_json();
+    /// assert_eq!(json, r#"{"rates":[{"from":"EUR","to":"USD","rate":"1.0850"}]}"#);
+    /// let reloaded = Exchange::from_json(&json).unwrap();
+    /// assert_eq!(reloaded.get_rate(&EUR, &USD), exchange.get_rate(&EUR, &USD));
+    /// ```
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self.table()).expect("a rate table serializes to JSON")
+    }
+
+    /// Reads an exchange written by `to_json`. It has the default policy and no provider.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::InvalidRateFile` if the JSON is not a rate table, holds an unknown currency code or a
+    /// historical rate that ends before it starts.
+    pub fn from_json(json: &str) -> Result<Exchange, MoneyError> {
+        serde_json::from_str(json).map_err(|err| MoneyError::InvalidRateFile(err.to_string()))
+    }
+
+    fn table(&self) -> RateTable {
+        let mut rates: Vec<TableRate> = self
+            .rates
+            .values()
+            .flat_map(HashMap::values)
+            .map(|edge| TableRate {
+                rate: edge.rate.clone(),
+                spread: edge.spread,
+            })
+            .collect();
+        rates.sort_by_key(|row| (row.rate.from.code, row.rate.to.code));
+        let mut history: Vec<TableHistory> = self
+            .history
+            .values()
+            .flat_map(|dated| dated.iter())
+            .map(|(date, dated)| TableHistory {
+                rate: dated.rate.clone(),
+                date: *date,
+                until: dated.until,
+            })
+            .collect();
+        history.sort_by_key(|row| (row.rate.from.code, row.rate.to.code, row.date));
+        RateTable { rates, history }
+    }
+
+    fn insert_dated(&mut self, rate: &ExchangeRate, date: NaiveDate, until: Option<NaiveDate>) {
+        let dated = DatedRate {
+            rate: rate.clone(),
+            until,
+        };
+        self.history.entry((rate.from, rate.to)).or_default().insert(date, dated);
+    }
+
+    /// Returns the rate for a currency pair as of a date, using the historical rates and the policy of the exchange.
+    ///
+    /// A rate without an end date is carried forward until the next one. Between rates, the `interpolation` of
+    /// the policy decides whether the earlier rate is used, the two are interpolated by days, or no rate is found.
+    /// After the last rate, interpolating carries it forward as well. Rates that were not stored for the date are
+    /// derived from the rates in effect on it, as in `lookup`.
+    pub fn lookup_at(&self, from: &Currency, to: &Currency, date: NaiveDate) -> Option<RateLookup> {
+        let interpolation = self.policy.interpolation;
+        // Historical rates have no spread, so the rate of the pair itself always wins
+        if let Some(rate) = self.history.get(&(*from, *to)).and_then(|dated| rate_on(dated, date, interpolation)) {
+            return Some(RateLookup {
+                rate: rate.clone(),
+                source: RateSource::Stored,
+                legs: vec![rate],
+            });
+        }
+        if !self.policy.allow_derived || self.policy.max_hops == 0 {
+            return None;
+        }
+        let rates = self.history.values().filter_map(|dated| rate_on(dated, date, interpolation));
+        search(&graph(rates.map(|rate| (rate, Decimal::ZERO))), from, to, &self.policy)
+    }
+
+    /// Converts an amount into `target` at the rate in effect on `date`, rounded with the `rounding` of the policy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::{Exchange, ExchangeRate, Money, MoneyError, NaiveDate};
+    /// use rusty_money::currencies::iso::{EUR, USD};
+    /// use rust_decimal_macros::dec;
+    ///
+    /// let mut exchange = Exchange::new();
+    /// let january = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    /// let february = NaiveDate::from_ymd_opt(2024, 2, 1).unwrap();
+    /// exchange.set_rate_at(&ExchangeRate::new(EUR, USD, dec!(1.10)).unwrap(), january);
+    /// exchange.set_rate_at(&ExchangeRate::new(EUR, USD, dec!(1.08)).unwrap(), february);
+    ///
+    /// let invoice = Money::from_major(100, EUR);
+    /// let paid = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+    /// assert_eq!(exchange.convert_at(&invoice, &USD, paid).unwrap(), Money::from_major(110, USD));
+    ///
+    /// let too_early = NaiveDate::from_ymd_opt(2023, 12, 31).unwrap();
+    /// assert_eq!(exchange.convert_at(&invoice, &USD, too_early), Err(MoneyError::MissingHistoricalRate));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::MissingHistoricalRate` if no rate, stored or derived, covers the pair on `date`.
+    pub fn convert_at<A: Amount>(
+        &self,
+        amount: &Money<A>,
+        target: &Currency,
+        date: NaiveDate,
+    ) -> Result<Money<A>, MoneyError> {
+        let lookup = if amount.currency() == target {
+            None
+        } else {
+            Some(self.lookup_at(amount.currency(), target, date).ok_or(MoneyError::MissingHistoricalRate)?)
+        };
+        self.report(amount, lookup).map(|report| report.converted)
+    }
+
+    /// Converts an amount into `target`, rounded with the `rounding` of the policy of the exchange.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::{Exchange, ExchangeRate, Money};
+    /// use rusty_money::currencies::iso::{EUR, USD};
+    /// use rust_decimal_macros::dec;
+    ///
+    /// let mut exchange = Exchange::new();
+    /// exchange.set_rate(&ExchangeRate::new(EUR, USD, dec!(1.0853)).unwrap());
+    ///
+    /// let converted = exchange.convert(&Money::from_minor(1_050, EUR), &USD).unwrap();
+    /// assert_eq!(converted, Money::from_minor(1_140, USD));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::MissingExchangeRate` if no rate is found for the pair, or the error of the provider of
+    /// the exchange if it failed.
+    pub fn convert<A: Amount>(&self, amount: &Money<A>, target: &Currency) -> Result<Money<A>, MoneyError> {
+        self.convert_with_report(amount, target).map(|report| report.converted)
+    }
+
+    /// Converts an amount into `target` like `convert`, and reports the unrounded result and the rate used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::{Exchange, ExchangeRate, Money, RateSource};
+    /// use rusty_money::currencies::iso::{EUR, USD};
+    /// use rust_decimal_macros::dec;
+    ///
+    /// let mut exchange = Exchange::new();
+    /// exchange.set_rate(&ExchangeRate::new(EUR, USD, dec!(1.0853)).unwrap());
+    ///
+    /// let report = exchange.convert_with_report(&Money::from_minor(1_050, EUR), &USD).unwrap();
+    /// assert_eq!(report.converted, Money::from_minor(1_140, USD));
+    /// assert_eq!(report.unrounded, Money::from_decimal(dec!(11.39565), USD));
+    /// assert_eq!(report.residual, Money::from_decimal(dec!(-0.00435), USD));
+    /// assert_eq!(report.lookup.unwrap().source, RateSource::Stored);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as `convert`.
+    pub fn convert_with_report<A: Amount>(
+        &self,
+        amount: &Money<A>,
+        target: &Currency,
+    ) -> Result<ConversionReport<A>, MoneyError> {
+        let lookup = if amount.currency() == target {
+            None
+        } else {
+            Some(self.try_lookup(amount.currency(), target)?)
+        };
+        self.report(amount, lookup)
+    }
+
+    fn report<A: Amount>(
+        &self,
+        amount: &Money<A>,
+        lookup: Option<RateLookup>,
+    ) -> Result<ConversionReport<A>, MoneyError> {
+        let unrounded = match &lookup {
+            Some(lookup) => lookup.convert(amount, self.policy.intermediate_rounding)?,
+            None => amount.clone(),
+        };
+        let converted = unrounded.round_with(self.policy.rounding);
+        let residual = unrounded.amount().clone() - converted.amount().clone();
+        Ok(ConversionReport {
+            residual: Money::from_amount(residual, *converted.currency()),
+            converted,
+            unrounded,
+            lookup,
+        })
+    }
+
+    /// Returns every stored rate and its inverse, by the currency they convert from.
+    fn legs(&self) -> &HashMap<Currency, Vec<Leg>> {
+        self.graph.get_or_init(|| {
+            let edges = self.rates.values().flat_map(HashMap::values);
+            graph(edges.map(|edge| (edge.rate.clone(), edge.spread.unwrap_or(Decimal::ZERO))))
+        })
+    }
+}
+
+/// Returns every rate and its inverse with their spreads, by the currency they convert from.
+fn graph<I>(rates: I) -> HashMap<Currency, Vec<Leg>>
+where
+    I: IntoIterator<Item = (ExchangeRate, Decimal)>,
+{
+    let mut legs: HashMap<Currency, Vec<Leg>> = HashMap::new();
+    for (rate, spread) in rates {
+        legs.entry(rate.from).or_default().push(Leg {
+            rate: rate.clone(),
+            spread,
+            inverted: false,
+        });
+        legs.entry(rate.to).or_default().push(Leg {
+            rate: rate.inverse(),
+            spread,
+            inverted: true,
+        });
+    }
+    legs
+}
+
+/// Finds the best path between two currencies over `legs` and the rate along it.
+fn search(
+    legs: &HashMap<Currency, Vec<Leg>>,
+    from: &Currency,
+    to: &Currency,
+    policy: &RatePolicy,
+) -> Option<RateLookup> {
+    let mut search = PathSearch {
+        legs,
+        to: *to,
+        policy,
+        best: None,
+    };
+    let mut path = Vec::new();
+    search.visit(from, &mut path, Decimal::ZERO);
+    let (_, path) = search.best?;
+
+    let legs: Vec<ExchangeRate> = path.iter().map(|leg| leg.rate.clone()).collect();
+    let source = match path.as_slice() {
+        [leg] if !leg.inverted => {
+            return Some(RateLookup {
+                rate: leg.rate.clone(),
+                source: RateSource::Stored,
+                legs,
+            })
+        }
+        [_] => RateSource::Inverted,
+        _ => RateSource::Triangulated,
+    };
+    let rate = legs.iter().try_fold(Decimal::ONE, |rate, leg| rate.checked_mul(leg.rate))?;
+    // A rate rounded away to nothing is no rate at all
+    let rate = policy.round(rate);
+    if rate <= Decimal::ZERO {
+        return None;
+    }
+    Some(RateLookup {
+        rate: ExchangeRate {
+            from: *from,
+            to: *to,
+            rate,
+        },
+        source,
+        legs,
+    })
+}
+
+/// Returns the historical rate in effect on `date`, if any.
+fn rate_on(
+    dated: &BTreeMap<NaiveDate, DatedRate>,
+    date: NaiveDate,
+    interpolation: Interpolation,
+) -> Option<ExchangeRate> {
+    let (start, earlier) = dated.range(..=date).next_back()?;
+    if *start == date {
+        return Some(earlier.rate.clone());
+    }
+    if let Some(until) = earlier.until {
+        return (date <= until).then(|| earlier.rate.clone());
+    }
+    match interpolation {
+        Interpolation::Exact => None,
+        Interpolation::Previous => Some(earlier.rate.clone()),
+        Interpolation::Linear => {
+            // After the last rate there is nothing to interpolate towards, so it is carried forward
+            let Some((end, later)) = dated.range(date..).next() else {
+                return Some(earlier.rate.clone());
+            };
+            let elapsed = Decimal::from((date - *start).num_days());
+            let span = Decimal::from((*end - *start).num_days());
+            let rate = earlier.rate.rate + (later.rate.rate - earlier.rate.rate) * elapsed / span;
+            Some(ExchangeRate { rate, ..earlier.rate.clone() })
+        }
+    }
+}
+
+/// Ranks paths by total spread, hops, whether they avoid the pivot, inverted legs, and finally by their currency
+/// codes so that the result does not depend on the order of the rates.
+type PathRank = (Decimal, usize, bool, usize, Vec<&'static str>);
+
+/// A depth-first search for the best path between two currencies.
+struct PathSearch<'a> {
+    legs: &'a HashMap<Currency, Vec<Leg>>,
+    to: Currency,
+    policy: &'a RatePolicy,
+    best: Option<(PathRank, Vec<&'a Leg>)>,
+}
+
+impl<'a> PathSearch<'a> {
+    fn visit(&mut self, at: &Currency, path: &mut Vec<&'a Leg>, spread: Decimal) {
+        let legs = self.legs;
+        for leg in legs.get(at).into_iter().flatten() {
+            let visited = path.iter().any(|step| step.rate.from == leg.rate.to);
+            if visited {
+                continue;
+            }
+            let spread = spread + leg.spread;
+            let hops = path.len() + 1;
+            // Paths only get more expensive and longer from here
+            if let Some(((best_spread, best_hops, ..), _)) = &self.best {
+                if spread > *best_spread || (spread == *best_spread && hops > *best_hops) {
+                    continue;
+                }
+            }
+
+            path.push(leg);
+            if leg.rate.to == self.to {
+                let rank = self.rank(path, spread);
+                if self.best.as_ref().is_none_or(|(best, _)| rank < *best) {
+                    self.best = Some((rank, path.clone()));
+                }
+            } else if hops < self.policy.max_hops {
+                self.visit(&leg.rate.to, path, spread);
+            }
+            path.pop();
+        }
+    }
+
+    fn rank(&self, path: &[&Leg], spread: Decimal) -> PathRank {
+        let inverted = path.iter().filter(|leg| leg.inverted).count();
+        let through_pivot = path[1..].iter().any(|leg| Some(leg.rate.from) == self.policy.pivot);
+        let codes = path.iter().map(|leg| leg.rate.to.code).collect();
+        (spread, path.len(), !through_pivot, inverted, codes)
+    }
+}
+
+/// Controls whether and how an `Exchange` derives rates that were not stored, and how it rounds conversions.
+///
+/// The default allows derived rates through at most one pivot currency and keeps them at the full precision of
+/// `Decimal`. Converted amounts are rounded to the target currency with banker's rounding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RatePolicy {
+    /// Whether rates that were not stored may be derived from the stored ones.
+    pub allow_derived: bool,
+    /// Decimal places to which derived rates are rounded, or `None` to keep full precision.
+    pub precision: Option<u32>,
+    /// Strategy used to round derived rates to `precision`.
+    pub strategy: RoundingStrategy,
+    /// Maximum number of stored or inverted rates chained to derive a rate.
+    pub max_hops: usize,
+    /// Currency preferred as pivot when several paths are equally cheap.
+    pub pivot: Option<Currency>,
+    /// Rounding applied to the amount in each intermediate currency when converting along a path.
+    pub intermediate_rounding: RoundingPolicy,
+    /// How historical rates are found for dates between two effective dates. Defaults to carrying the earlier
+    /// rate forward.
+    pub interpolation: Interpolation,
+    /// Rounding applied to amounts converted by the exchange.
+    pub rounding: RoundingPolicy,
+}
+
+impl RatePolicy {
+    pub fn new() -> RatePolicy {
+        RatePolicy::default()
+    }
+
+    /// Returns a policy that only answers with stored rates.
+    pub fn stored_only() -> RatePolicy {
+        RatePolicy {
+            allow_derived: false,
+            ..RatePolicy::default()
+        }
+    }
+
+    /// Returns the policy with derived rates rounded to `precision` decimal places.
+    pub fn with_precision(mut self, precision: u32, strategy: RoundingStrategy) -> RatePolicy {
+        self.precision = Some(precision);
+        self.strategy = strategy;
+        self
+    }
+
+    /// Returns the policy with derived rates chaining at most `max_hops` rates.
+    pub fn with_max_hops(mut self, max_hops: usize) -> RatePolicy {
+        self.max_hops = max_hops;
+        self
+    }
+
+    /// Returns the policy with a preferred pivot currency.
+    pub fn with_pivot(mut self, pivot: Currency) -> RatePolicy {
+        self.pivot = Some(pivot);
+        self
+    }
+
+    /// Returns the policy with amounts rounded in every intermediate currency of a path.
+    pub fn with_intermediate_rounding(mut self, rounding: RoundingPolicy) -> RatePolicy {
+        self.intermediate_rounding = rounding;
+        self
+    }
+
+    /// Returns the policy with converted amounts rounded with `rounding`.
+    pub fn with_rounding(mut self, rounding: RoundingPolicy) -> RatePolicy {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Returns the policy with historical rates found with `interpolation`.
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> RatePolicy {
+        self.interpolation = interpolation;
+        self
+    }
+
+    fn round(&self, rate: Decimal) -> Decimal {
+        match self.precision {
+            Some(precision) => rate.round_dp_with_strategy(precision, self.strategy),
+            None => rate,
+        }
+    }
+}
+
+impl Default for RatePolicy {
+    fn default() -> Self {
+        RatePolicy {
+            allow_derived: true,
+            precision: None,
+            strategy: RoundingStrategy::MidpointNearestEven,
+            max_hops: 2,
+            pivot: None,
+            intermediate_rounding: RoundingPolicy::Unrounded,
+            interpolation: Interpolation::Previous,
+            rounding: RoundingPolicy::ToCurrency(RoundingStrategy::MidpointNearestEven),
+        }
+    }
+}
+
+/// Where a rate returned by an `Exchange` came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateSource {
+    /// The rate was stored with `Exchange::set_rate`.
+    Stored,
+    /// The rate was derived by inverting the stored rate of the reverse pair.
+    Inverted,
+    /// The rate was derived by chaining rates through one or more pivot currencies.
+    Triangulated,
+    /// The rate was returned by the `RateProvider` of the exchange.
+    Provided,
+}
+
+/// A rate returned by `Exchange::lookup`, together with how it was found.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RateLookup {
+    pub rate: ExchangeRate,
+    pub source: RateSource,
+    /// The stored or inverted rates chained to get `rate`, at full precision.
+    pub legs: Vec<ExchangeRate>,
+}
+
+impl RateLookup {
+    /// Returns true if the rate was not stored in the exchange but derived from other rates.
+    pub fn is_derived(&self) -> bool {
+        matches!(self.source, RateSource::Inverted | RateSource::Triangulated)
+    }
+
+    /// Converts an amount along the path of this lookup.
+    ///
+    /// With `RoundingPolicy::Unrounded` the amount is converted at `rate` in a single step. Otherwise it is
+    /// converted leg by leg and rounded with `intermediate` in every intermediate currency, as a chain of real
+    /// conversions would be. The result itself is not rounded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::{Exchange, ExchangeRate, Money, RoundingPolicy};
+    /// use rusty_money::currencies::iso::{EUR, GBP, USD};
+    /// use rust_decimal::RoundingStrategy;
+    /// use rust_decimal_macros::dec;
+    ///
+    /// let mut exchange = Exchange::new();
+    /// exchange.set_rate(&ExchangeRate::new(GBP, EUR, dec!(1.17)).unwrap());
+    /// exchange.set_rate(&ExchangeRate::new(EUR, USD, dec!(1.09)).unwrap());
+    ///
+    /// let lookup = exchange.lookup(&GBP, &USD).unwrap();
+    /// let amount = Money::from_minor(1_001, GBP);
+    /// let cents = RoundingPolicy::ToCurrency(RoundingStrategy::MidpointNearestEven);
+    /// let exact = lookup.convert(&amount, RoundingPolicy::Unrounded).unwrap();
+    /// assert_eq!(exact, Money::from_decimal(dec!(12.765753), USD));
+    /// assert_eq!(lookup.convert(&amount, cents).unwrap(), Money::from_decimal(dec!(12.7639), USD));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::InvalidCurrency` if `amount` is not in the currency the rate converts from.
+    pub fn convert<A: Amount>(&self, amount: &Money<A>, intermediate: RoundingPolicy) -> Result<Money<A>, MoneyError> {
+        if intermediate == RoundingPolicy::Unrounded {
+            return self.rate.convert(amount);
+        }
+        let (last, intermediates) = self.legs.split_last().ok_or(MoneyError::MissingExchangeRate)?;
+        if amount.currency() != &self.rate.from {
+            return Err(MoneyError::InvalidCurrency);
+        }
+        let mut converted = amount.clone();
+        for leg in intermediates {
+            converted = leg.convert(&converted)?.round_with(intermediate);
+        }
+        last.convert(&converted)
+    }
+}
+
+/// The result of converting with an `Exchange`, before and after rounding.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConversionReport<A = Decimal> {
+    /// The converted amount, rounded with the policy of the exchange.
+    pub converted: Money<A>,
+    /// The converted amount before rounding.
+    pub unrounded: Money<A>,
+    /// What rounding removed, so that `converted` plus `residual` equals `unrounded`.
+    pub residual: Money<A>,
+    /// The rate used, or `None` if the amount was already in the target currency.
+    pub lookup: Option<RateLookup>,
+}
+
+impl<A: Amount> Money<A> {
+    /// Converts this amount into `target` with the rates of an exchange. See `Exchange::convert`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::{Exchange, ExchangeRate, Money};
+    /// use rusty_money::currencies::iso::{EUR, JPY};
+    /// use rust_decimal_macros::dec;
+    ///
+    /// let mut exchange = Exchange::new();
+    /// exchange.set_rate(&ExchangeRate::new(EUR, JPY, dec!(161.234)).unwrap());
+    ///
+    /// let yen = Money::from_minor(1_050, EUR).convert_with(&exchange, &JPY).unwrap();
+    /// assert_eq!(yen, Money::from_major(1_693, JPY));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as `Exchange::convert`.
+    pub fn convert_with(&self, exchange: &Exchange, target: &Currency) -> Result<Money<A>, MoneyError> {
+        exchange.convert(self, target)
     }
 }
 
 /// Stores rates of conversion between two currencies.
-#[derive(Debug, PartialEq, Clone)] // Removed Copy
+///
+/// A rate serializes as `{"from": "EUR", "to": "USD", "rate": "1.0850"}`, with the rate as a string so that no
+/// precision is lost.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)] // Removed Copy
+#[serde(try_from = "RateRecord", into = "RateRecord")]
 pub struct ExchangeRate {
     pub from: Currency,
     pub to: Currency,
@@ -43,32 +906,252 @@ pub struct ExchangeRate {
 }
 
 impl ExchangeRate {
+    /// Creates a rate at which one unit of `from` buys `rate` units of `to`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::InvalidCurrency` if `from` and `to` are the same, and `MoneyError::InvalidExchangeRate`
+    /// if `rate` is not positive.
     pub fn new(from: Currency, to: Currency, rate: Decimal) -> Result<ExchangeRate, MoneyError> {
         if from == to {
             return Err(MoneyError::InvalidCurrency);
         }
+        if rate <= Decimal::ZERO {
+            return Err(MoneyError::InvalidExchangeRate);
+        }
         Ok(ExchangeRate { from, to, rate })
     }
 
+    /// Creates a rate from a floating point value, such as one read from a JSON feed.
+    ///
+    /// The value is taken as the shortest decimal that prints as the same `f64`, so `1.085` becomes exactly 1.085.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::InvalidExchangeRate` if `rate` is NaN, infinite or not positive, and
+    /// `MoneyError::Overflow` if it is too large for a `Decimal`.
+    pub fn try_from_f64(from: Currency, to: Currency, rate: f64) -> Result<ExchangeRate, MoneyError> {
+        if !rate.is_finite() {
+            return Err(MoneyError::InvalidExchangeRate);
+        }
+        let rate = Decimal::from_str(&rate.to_string()).map_err(|_| MoneyError::Overflow)?;
+        ExchangeRate::new(from, to, rate)
+    }
+
+    /// Returns the number of units of `to` that one unit of `from` buys.
+    pub fn rate(&self) -> Decimal {
+        self.rate
+    }
+
+    /// Returns the rate of the reverse pair, at full precision.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::ExchangeRate;
+    /// use rusty_money::currencies::iso::{EUR, USD};
+    /// use rust_decimal_macros::dec;
+    ///
+    /// let inverse = ExchangeRate::new(EUR, USD, dec!(1.25)).unwrap().inverse();
+    /// assert_eq!(inverse, ExchangeRate::new(USD, EUR, dec!(0.8)).unwrap());
+    /// ```
+    pub fn inverse(&self) -> ExchangeRate {
+        ExchangeRate {
+            from: self.to,
+            to: self.from,
+            rate: Decimal::ONE / self.rate,
+        }
+    }
+
+    /// Chains this rate with a rate from its `to` currency, at full precision.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::ExchangeRate;
+    /// use rusty_money::currencies::iso::{EUR, JPY, USD};
+    /// use rust_decimal_macros::dec;
+    ///
+    /// let eur_usd = ExchangeRate::new(EUR, USD, dec!(1.08)).unwrap();
+    /// let usd_jpy = ExchangeRate::new(USD, JPY, dec!(150)).unwrap();
+    /// assert_eq!(eur_usd.compose(&usd_jpy), ExchangeRate::new(EUR, JPY, dec!(162)));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::InvalidCurrency` if `other` does not convert from the `to` currency of this rate or
+    /// converts back to its `from` currency, `MoneyError::Overflow` if the combined rate is too large for a
+    /// `Decimal` and `MoneyError::InvalidExchangeRate` if it is too small.
+    pub fn compose(&self, other: &ExchangeRate) -> Result<ExchangeRate, MoneyError> {
+        if other.from != self.to {
+            return Err(MoneyError::InvalidCurrency);
+        }
+        let rate = self.rate.checked_mul(other.rate).ok_or(MoneyError::Overflow)?;
+        ExchangeRate::new(self.from, other.to, rate)
+    }
+
     /// Converts a Money from one Currency to another using the exchange rate.
-    pub fn convert(&self, amount: &Money) -> Result<Money, MoneyError> {
+    ///
+    /// Works with any amount backend; the rate is applied with `Amount::mul_decimal`.
+    pub fn convert<A: Amount>(&self, amount: &Money<A>) -> Result<Money<A>, MoneyError> {
         if amount.currency() != &self.from {
             return Err(MoneyError::InvalidCurrency);
         }
-        let converted_amount = amount.amount() * self.rate;
-        // Clone self.to as Money::from_decimal takes Currency by value
-        Ok(Money::from_decimal(converted_amount, self.to.clone()))
+        let converted_amount = amount.amount().mul_decimal(self.rate);
+        Ok(Money::from_amount(converted_amount, self.to))
+    }
+}
+
+impl fmt::Display for ExchangeRate {
+    /// Writes the rate as "1 EUR = 1.0850 USD".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "1 {} = {} {}", self.from.code, self.rate, self.to.code)
+    }
+}
+
+impl FromStr for ExchangeRate {
+    type Err = MoneyError;
+
+    /// Parses a rate written as "EUR/USD 1.0850", or as "1 EUR = 1.0850 USD" like `Display` writes it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::ExchangeRate;
+    /// use rusty_money::currencies::iso::{EUR, USD};
+    /// use rust_decimal_macros::dec;
+    ///
+    /// let rate: ExchangeRate = "EUR/USD 1.0850".parse().unwrap();
+    /// assert_eq!(rate, ExchangeRate::new(EUR, USD, dec!(1.0850)).unwrap());
+    /// assert_eq!(rate.to_string(), "1 EUR = 1.0850 USD");
+    /// assert_eq!(rate.to_string().parse(), Ok(rate));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::InvalidAmountFormat` if the string has neither form, `MoneyError::InvalidAmountDecimal`
+    /// if the rate is not a decimal number, `MoneyError::UnknownCurrency` if a code is not recognized, and the
+    /// errors of `ExchangeRate::new`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split_whitespace().collect();
+        let (from, to, rate) = match parts[..] {
+            [pair, rate] => {
+                let (from, to) = pair.split_once('/').ok_or(MoneyError::InvalidAmountFormat)?;
+                (from, to, rate)
+            }
+            ["1", from, "=", rate, to] => (from, to, rate),
+            _ => return Err(MoneyError::InvalidAmountFormat),
+        };
+        let currency = |code: &str| find_currency(code).copied().ok_or(MoneyError::UnknownCurrency(code.to_string()));
+        let rate = Decimal::from_str(rate).map_err(MoneyError::InvalidAmountDecimal)?;
+        ExchangeRate::new(currency(from)?, currency(to)?, rate)
+    }
+}
+
+/// The serialized form of an `ExchangeRate`, with currencies as codes.
+#[derive(Serialize, Deserialize)]
+struct RateRecord {
+    from: String,
+    to: String,
+    rate: Decimal,
+}
+
+impl TryFrom<RateRecord> for ExchangeRate {
+    type Error = MoneyError;
+
+    fn try_from(record: RateRecord) -> Result<Self, Self::Error> {
+        let currency = |code: String| find_currency(&code).copied().ok_or(MoneyError::UnknownCurrency(code));
+        ExchangeRate::new(currency(record.from)?, currency(record.to)?, record.rate)
+    }
+}
+
+impl From<ExchangeRate> for RateRecord {
+    fn from(rate: ExchangeRate) -> Self {
+        RateRecord {
+            from: rate.from.code.to_string(),
+            to: rate.to.code.to_string(),
+            rate: rate.rate,
+        }
+    }
+}
+
+/// The serialized form of an `Exchange`.
+#[derive(Serialize, Deserialize)]
+struct RateTable {
+    rates: Vec<TableRate>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    history: Vec<TableHistory>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TableRate {
+    #[serde(flatten)]
+    rate: ExchangeRate,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    spread: Option<Decimal>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TableHistory {
+    #[serde(flatten)]
+    rate: ExchangeRate,
+    date: NaiveDate,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    until: Option<NaiveDate>,
+}
+
+impl TryFrom<RateTable> for Exchange {
+    type Error = MoneyError;
+
+    fn try_from(table: RateTable) -> Result<Self, Self::Error> {
+        let mut exchange = Exchange::new();
+        for row in table.rates {
+            match row.spread {
+                Some(spread) => exchange.set_rate_with_spread(&row.rate, spread)?,
+                None => exchange.set_rate(&row.rate),
+            }
+        }
+        for row in table.history {
+            match row.until {
+                Some(until) => exchange.set_rate_between(&row.rate, row.date, until)?,
+                None => exchange.set_rate_at(&row.rate, row.date),
+            }
+        }
+        Ok(exchange)
+    }
+}
+
+impl Serialize for Exchange {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.table().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Exchange {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Exchange::try_from(RateTable::deserialize(deserializer)?).map_err(de::Error::custom)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::find_currency;
+    use crate::{find_currency, FixedPoint, StaticRates};
     use rust_decimal_macros::*;
-    use crate::currencies::iso::{EUR, GBP, USD};
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+    use crate::currencies::iso::{CHF, EUR, GBP, JPY, USD};
 
     #[test]
+    #[allow(clippy::clone_on_copy, clippy::needless_borrow)] // The original test body is kept unchanged
     fn exchange_stores_rates() {
         let usd = find_currency("USD").unwrap();
         let eur = find_currency("EUR").unwrap();
@@ -89,6 +1172,287 @@ mod tests {
         assert_eq!(fetched_rate.rate, dec!(1.6));
     }
 
+    #[test]
+    fn exchange_derives_inverse_rates() {
+        let mut exchange = Exchange::new();
+        exchange.set_rate(&ExchangeRate::new(EUR, USD, dec!(1.0850)).unwrap());
+
+        let stored = exchange.lookup(&EUR, &USD).unwrap();
+        assert_eq!(stored.source, RateSource::Stored);
+        assert!(!stored.is_derived());
+
+        let inverted = exchange.lookup(&USD, &EUR).unwrap();
+        assert_eq!(inverted.source, RateSource::Inverted);
+        assert_eq!(inverted.rate.rate, dec!(1) / dec!(1.0850));
+        assert_eq!(exchange.get_rate(&USD, &EUR), Some(inverted.rate));
+
+        // A stored reverse rate wins over the inverted one
+        exchange.set_rate(&ExchangeRate::new(USD, EUR, dec!(0.92)).unwrap());
+        assert_eq!(exchange.lookup(&USD, &EUR).unwrap().source, RateSource::Stored);
+        assert_eq!(exchange.get_rate(&USD, &EUR).unwrap().rate, dec!(0.92));
+
+        assert_eq!(exchange.get_rate(&USD, &GBP), None);
+        assert_eq!(exchange.get_rate(&USD, &USD), None);
+    }
+
+    #[test]
+    fn exchange_rate_policy() {
+        let mut exchange = Exchange::new().with_policy(RatePolicy::stored_only());
+        exchange.set_rate(&ExchangeRate::new(GBP, USD, dec!(1.2345)).unwrap());
+        exchange.set_rate(&ExchangeRate::new(USD, JPY, dec!(150)).unwrap());
+        assert_eq!(exchange.get_rate(&USD, &GBP), None);
+
+        exchange.set_policy(RatePolicy::new().with_precision(2, RoundingStrategy::ToZero));
+        assert_eq!(exchange.policy().precision, Some(2));
+        assert_eq!(exchange.get_rate(&USD, &GBP).unwrap().rate, dec!(0.81));
+        // Stored rates are never rounded
+        assert_eq!(exchange.get_rate(&GBP, &USD).unwrap().rate, dec!(1.2345));
+        // A derived rate that rounds to zero is not returned
+        assert_eq!(exchange.get_rate(&JPY, &USD), None);
+    }
+
+    #[test]
+    fn exchange_triangulates_through_pivots() {
+        let mut exchange = Exchange::new();
+        exchange.set_rate(&ExchangeRate::new(EUR, GBP, dec!(0.8)).unwrap());
+        exchange.set_rate(&ExchangeRate::new(EUR, USD, dec!(1.1)).unwrap());
+        exchange.set_rate(&ExchangeRate::new(USD, JPY, dec!(150)).unwrap());
+        exchange.set_rate(&ExchangeRate::new(EUR, CHF, dec!(0.95)).unwrap());
+        exchange.set_rate(&ExchangeRate::new(CHF, USD, dec!(1.16)).unwrap());
+
+        let lookup = exchange.lookup(&GBP, &USD).unwrap();
+        assert_eq!(lookup.source, RateSource::Triangulated);
+        assert!(lookup.is_derived());
+        assert_eq!(lookup.rate.rate, dec!(1.375));
+        assert_eq!(lookup.legs.iter().map(|leg| leg.to.code).collect::<Vec<_>>(), ["EUR", "USD"]);
+
+        // GBP -> JPY needs three hops, more than the default allows
+        assert_eq!(exchange.lookup(&GBP, &JPY), None);
+        let three_hops = RatePolicy::new().with_max_hops(3);
+        assert_eq!(exchange.lookup_with(&GBP, &JPY, &three_hops).unwrap().rate.rate, dec!(206.25));
+        assert_eq!(exchange.lookup_with(&GBP, &JPY, &RatePolicy::new().with_max_hops(1)), None);
+
+        // EUR -> USD is stored, so no pivot is needed; USD -> EUR is the inverse
+        assert_eq!(exchange.lookup(&EUR, &USD).unwrap().source, RateSource::Stored);
+        assert_eq!(exchange.lookup(&USD, &EUR).unwrap().source, RateSource::Inverted);
+
+        // CHF -> JPY goes through USD directly, or through EUR with an inverted leg
+        let through_usd = exchange.lookup(&CHF, &JPY).unwrap();
+        assert_eq!(through_usd.rate.rate, dec!(174));
+        let prefer_eur = three_hops.with_pivot(EUR);
+        assert_eq!(exchange.lookup_with(&CHF, &JPY, &prefer_eur).unwrap().rate.rate, dec!(174));
+    }
+
+    #[test]
+    fn exchange_prefers_cheapest_path() {
+        let mut exchange = Exchange::new();
+        exchange.set_rate_with_spread(&ExchangeRate::new(GBP, JPY, dec!(190)).unwrap(), dec!(0.02)).unwrap();
+        exchange.set_rate_with_spread(&ExchangeRate::new(GBP, USD, dec!(1.27)).unwrap(), dec!(0.001)).unwrap();
+        exchange.set_rate_with_spread(&ExchangeRate::new(USD, JPY, dec!(150)).unwrap(), dec!(0.001)).unwrap();
+        exchange.set_rate(&ExchangeRate::new(GBP, EUR, dec!(1.17)).unwrap());
+        exchange.set_rate(&ExchangeRate::new(EUR, JPY, dec!(162)).unwrap());
+        exchange.set_rate(&ExchangeRate::new(GBP, CHF, dec!(1.1)).unwrap());
+        exchange.set_rate(&ExchangeRate::new(CHF, JPY, dec!(173)).unwrap());
+
+        // The stored GBP -> JPY rate is the most expensive, and the free paths through EUR and CHF tie
+        let cheapest = exchange.lookup(&GBP, &JPY).unwrap();
+        assert_eq!(cheapest.legs.iter().map(|leg| leg.to.code).collect::<Vec<_>>(), ["CHF", "JPY"]);
+        let pivot = RatePolicy::new().with_pivot(EUR);
+        assert_eq!(exchange.lookup_with(&GBP, &JPY, &pivot).unwrap().rate.rate, dec!(189.54));
+
+        exchange.set_rate_with_spread(&ExchangeRate::new(GBP, EUR, dec!(1.17)).unwrap(), dec!(0.01)).unwrap();
+        exchange.set_rate_with_spread(&ExchangeRate::new(GBP, CHF, dec!(1.1)).unwrap(), dec!(0.01)).unwrap();
+        assert_eq!(exchange.lookup(&GBP, &JPY).unwrap().rate.rate, dec!(190.5));
+
+        let quote: FxQuote = FxQuote::new(GBP, USD, dec!(1.249), dec!(1.251)).unwrap();
+        exchange.set_quote(&quote);
+        assert_eq!(exchange.get_rate(&GBP, &USD).unwrap().rate, dec!(1.25));
+        assert_eq!(exchange.lookup(&GBP, &JPY).unwrap().rate.rate, dec!(187.5));
+    }
+
+    #[test]
+    fn exchange_ranks_pivot_before_inverted_legs() {
+        let mut exchange = Exchange::new();
+        exchange.set_rate(&ExchangeRate::new(GBP, USD, dec!(1.25)).unwrap());
+        exchange.set_rate(&ExchangeRate::new(USD, JPY, dec!(150)).unwrap());
+        exchange.set_rate(&ExchangeRate::new(GBP, EUR, dec!(1.2)).unwrap());
+        exchange.set_rate(&ExchangeRate::new(JPY, EUR, dec!(0.00625)).unwrap());
+
+        // Without a pivot the path without inverted rates wins, with one the path through the pivot does
+        assert_eq!(exchange.lookup(&GBP, &JPY).unwrap().rate.rate, dec!(187.5));
+        let pivot = RatePolicy::new().with_pivot(EUR);
+        assert_eq!(exchange.lookup_with(&GBP, &JPY, &pivot).unwrap().rate.rate, dec!(192));
+
+        // A stored rate without a spread is returned before any search
+        assert_eq!(exchange.lookup_with(&GBP, &USD, &pivot).unwrap().source, RateSource::Stored);
+    }
+
+    #[test]
+    fn exchange_rejects_negative_spreads() {
+        let mut exchange = Exchange::new();
+        let rate = ExchangeRate::new(GBP, USD, dec!(1.27)).unwrap();
+        assert_eq!(exchange.set_rate_with_spread(&rate, dec!(-0.001)), Err(MoneyError::InvalidSpread));
+        assert_eq!(exchange.get_rate(&GBP, &USD), None);
+        assert_eq!(exchange.set_rate_with_spread(&rate, dec!(0)), Ok(()));
+        assert_eq!(exchange.get_rate(&GBP, &USD), Some(rate));
+
+        let json = exchange.to_json().replace("\"0\"", "\"-0.5\"");
+        let error = MoneyError::InvalidRateFile(MoneyError::InvalidSpread.to_string());
+        assert_eq!(Exchange::from_json(&json).unwrap_err(), error);
+    }
+
+    #[test]
+    fn lookup_converts_with_intermediate_rounding() {
+        let mut exchange = Exchange::new();
+        exchange.set_rate(&ExchangeRate::new(EUR, JPY, dec!(161.234)).unwrap());
+        exchange.set_rate(&ExchangeRate::new(EUR, GBP, dec!(0.85)).unwrap());
+
+        let lookup = exchange.lookup(&JPY, &GBP).unwrap();
+        let yen = Money::from_major(1_000, JPY);
+        let cents = RoundingPolicy::ToCurrency(RoundingStrategy::MidpointNearestEven);
+        let exact = lookup.convert(&yen, RoundingPolicy::Unrounded).unwrap();
+        assert_eq!(exact.round_with(cents), Money::from_minor(527, GBP));
+        // 1000 JPY is 6.20 EUR after rounding, which is 5.27 GBP
+        assert_eq!(lookup.convert(&yen, cents).unwrap(), Money::from_decimal(dec!(5.270), GBP));
+        assert_eq!(lookup.convert(&Money::from_major(1, EUR), cents), Err(MoneyError::InvalidCurrency));
+    }
+
+    #[test]
+    fn exchange_historical_rates() {
+        let mut exchange = Exchange::new();
+        exchange.load_history([
+            (date(2024, 1, 1), ExchangeRate::new(EUR, USD, dec!(1.10)).unwrap()),
+            (date(2024, 1, 11), ExchangeRate::new(EUR, USD, dec!(1.20)).unwrap()),
+            (date(2024, 1, 1), ExchangeRate::new(EUR, GBP, dec!(0.85)).unwrap()),
+        ]);
+        let usd_jpy = ExchangeRate::new(USD, JPY, dec!(150)).unwrap();
+        exchange.set_rate_between(&usd_jpy, date(2024, 1, 5), date(2024, 1, 6)).unwrap();
+        // Current rates are not used for dated conversions
+        exchange.set_rate(&ExchangeRate::new(EUR, USD, dec!(2)).unwrap());
+
+        let rate_on = |exchange: &Exchange, from, to, day| {
+            let lookup = exchange.lookup_at(from, to, date(2024, 1, day));
+            lookup.map(|lookup| lookup.rate.rate.round_dp(20))
+        };
+        assert_eq!(rate_on(&exchange, &EUR, &USD, 1), Some(dec!(1.10)));
+        assert_eq!(rate_on(&exchange, &EUR, &USD, 6), Some(dec!(1.10)));
+        assert_eq!(rate_on(&exchange, &EUR, &USD, 30), Some(dec!(1.20)));
+        assert_eq!(rate_on(&exchange, &USD, &EUR, 11), Some((dec!(1) / dec!(1.20)).round_dp(20)));
+        assert_eq!(rate_on(&exchange, &GBP, &USD, 1), Some((dec!(1.10) / dec!(0.85)).round_dp(20)));
+        assert_eq!(rate_on(&exchange, &EUR, &JPY, 5), Some(dec!(165.0)));
+        assert_eq!(rate_on(&exchange, &EUR, &JPY, 7), None);
+
+        exchange.set_policy(RatePolicy::new().with_interpolation(Interpolation::Linear));
+        assert_eq!(rate_on(&exchange, &EUR, &USD, 6), Some(dec!(1.15)));
+        assert_eq!(rate_on(&exchange, &EUR, &USD, 12), Some(dec!(1.20)));
+        assert_eq!(rate_on(&exchange, &USD, &JPY, 6), Some(dec!(150)));
+        exchange.set_policy(RatePolicy::new().with_interpolation(Interpolation::Exact));
+        assert_eq!(rate_on(&exchange, &EUR, &USD, 6), None);
+        assert_eq!(rate_on(&exchange, &EUR, &USD, 11), Some(dec!(1.20)));
+
+        let invoice = Money::from_major(100, EUR);
+        assert_eq!(exchange.convert_at(&invoice, &USD, date(2024, 1, 11)), Ok(Money::from_major(120, USD)));
+        assert_eq!(exchange.convert_at(&invoice, &EUR, date(2020, 1, 1)), Ok(invoice.clone()));
+        let missing = exchange.convert_at(&invoice, &USD, date(2024, 1, 12));
+        assert_eq!(missing, Err(MoneyError::MissingHistoricalRate));
+        let backwards = exchange.set_rate_between(&usd_jpy, date(2024, 1, 5), date(2024, 1, 4));
+        assert_eq!(backwards, Err(MoneyError::InvalidDateRange));
+    }
+
+    #[test]
+    fn exchange_converts_with_rounding() {
+        let mut exchange = Exchange::new();
+        exchange.set_rate(&ExchangeRate::new(EUR, USD, dec!(1.0853)).unwrap());
+        exchange.set_rate(&ExchangeRate::new(USD, JPY, dec!(151.37)).unwrap());
+
+        let amount = Money::from_minor(1_050, EUR);
+        assert_eq!(exchange.convert(&amount, &USD), Ok(Money::from_minor(1_140, USD)));
+        assert_eq!(amount.convert_with(&exchange, &JPY), Ok(Money::from_major(1_725, JPY)));
+        assert_eq!(amount.convert_with(&exchange, &EUR), Ok(amount.clone()));
+        assert_eq!(amount.convert_with(&exchange, &GBP), Err(MoneyError::MissingExchangeRate));
+
+        let report = exchange.convert_with_report(&Money::from_major(1_000, JPY), &EUR).unwrap();
+        assert_eq!(report.converted, Money::from_minor(609, EUR));
+        assert_eq!(report.lookup.unwrap().source, RateSource::Triangulated);
+        let back = (report.converted.clone() + report.residual.clone()).unwrap();
+        assert_eq!(back, report.unrounded);
+
+        let same = exchange.convert_with_report(&Money::from_decimal(dec!(1.005), EUR), &EUR).unwrap();
+        assert_eq!(same.converted, Money::from_minor(100, EUR));
+        assert_eq!(same.residual, Money::from_decimal(dec!(0.005), EUR));
+        assert_eq!(same.lookup, None);
+
+        exchange.set_policy(RatePolicy::new().with_rounding(RoundingPolicy::Unrounded));
+        assert_eq!(exchange.convert(&amount, &USD), Ok(Money::from_decimal(dec!(11.39565), USD)));
+        let cents = RoundingPolicy::ToDecimalPlaces(1, RoundingStrategy::ToZero);
+        exchange.set_policy(RatePolicy::new().with_rounding(cents));
+        assert_eq!(exchange.convert(&amount, &USD), Ok(Money::from_decimal(dec!(11.3), USD)));
+
+        let fixed: Money<FixedPoint<4>> = Money::parse("10.50 EUR").unwrap();
+        let rates = StaticRates::new().with_rate(ExchangeRate::new(EUR, USD, dec!(1.0853)).unwrap());
+        let exchange = Exchange::new().with_provider(rates);
+        assert_eq!(fixed.convert_with(&exchange, &USD), Ok(Money::parse("11.40 USD").unwrap()));
+    }
+
+    #[test]
+    fn exchange_serde_roundtrip() {
+        let rate = ExchangeRate::new(EUR, USD, dec!(1.0850)).unwrap();
+        let json = serde_json::to_string(&rate).unwrap();
+        assert_eq!(json, r#"{"from":"EUR","to":"USD","rate":"1.0850"}"#);
+        assert_eq!(serde_json::from_str::<ExchangeRate>(&json).unwrap(), rate);
+        let number = serde_json::from_str::<ExchangeRate>(r#"{"from":"EUR","to":"USD","rate":1.25}"#).unwrap();
+        assert_eq!(number.rate(), dec!(1.25));
+
+        let mut exchange = Exchange::new().with_policy(RatePolicy::stored_only());
+        exchange.set_rate(&rate);
+        exchange.set_rate_with_spread(&ExchangeRate::new(GBP, EUR, dec!(1.17)).unwrap(), dec!(0.002)).unwrap();
+        exchange.set_rate_at(&ExchangeRate::new(EUR, USD, dec!(1.09)).unwrap(), date(2024, 1, 2));
+        let until = ExchangeRate::new(EUR, USD, dec!(1.10)).unwrap();
+        exchange.set_rate_between(&until, date(2024, 1, 5), date(2024, 1, 6)).unwrap();
+        exchange.set_rate_at(&ExchangeRate::new(CHF, EUR, dec!(1.05)).unwrap(), date(2024, 1, 3));
+
+        let json = exchange.to_json();
+        let expected = concat!(
+            r#"{"rates":[{"from":"EUR","to":"USD","rate":"1.0850"},"#,
+            r#"{"from":"GBP","to":"EUR","rate":"1.17","spread":"0.002"}],"#,
+            r#""history":[{"from":"CHF","to":"EUR","rate":"1.05","date":"2024-01-03"},"#,
+            r#"{"from":"EUR","to":"USD","rate":"1.09","date":"2024-01-02"},"#,
+            r#"{"from":"EUR","to":"USD","rate":"1.10","date":"2024-01-05","until":"2024-01-06"}]}"#
+        );
+        assert_eq!(json, expected);
+
+        // Reloading keeps every rate and spread, but not the policy
+        let reloaded = Exchange::from_json(&json).unwrap();
+        assert_eq!(reloaded.to_json(), json);
+        assert_eq!(reloaded.policy(), &RatePolicy::default());
+        assert_eq!(reloaded.lookup_at(&EUR, &USD, date(2024, 1, 7)), None);
+        assert_eq!(reloaded.lookup_at(&EUR, &USD, date(2024, 1, 4)).unwrap().rate.rate, dec!(1.09));
+        assert_eq!(reloaded.get_rate(&GBP, &USD).unwrap().rate, dec!(1.269450));
+        let shared = crate::SharedExchange::new(reloaded);
+        assert_eq!(serde_json::to_string(shared.snapshot().exchange()).unwrap(), json);
+
+        let lookup = exchange.lookup(&EUR, &USD).unwrap();
+        assert_eq!(serde_json::from_str::<RateLookup>(&serde_json::to_string(&lookup).unwrap()).unwrap(), lookup);
+        assert_eq!(Exchange::from_json(r#"{"rates":[]}"#).unwrap().to_json(), r#"{"rates":[]}"#);
+    }
+
+    #[test]
+    fn exchange_serde_errors() {
+        let unknown = serde_json::from_str::<ExchangeRate>(r#"{"from":"EUR","to":"XYZ","rate":"1"}"#);
+        assert_eq!(unknown.unwrap_err().to_string(), "Unknown currency code: XYZ");
+        assert!(serde_json::from_str::<ExchangeRate>(r#"{"from":"EUR","to":"EUR","rate":"1"}"#).is_err());
+        assert!(serde_json::from_str::<ExchangeRate>(r#"{"from":"EUR","to":"USD"}"#).is_err());
+
+        let reversed = concat!(
+            r#"{"rates":[],"history":[{"from":"EUR","to":"USD","rate":"1","#,
+            r#""date":"2024-01-02","until":"2024-01-01"}]}"#
+        );
+        let error = Exchange::from_json(reversed).unwrap_err();
+        assert_eq!(error, MoneyError::InvalidRateFile("End date is before start date".to_string()));
+        assert!(matches!(Exchange::from_json(r#"{"rates":[{"from":"EUR"}]}"#), Err(MoneyError::InvalidRateFile(_))));
+        assert!(matches!(Exchange::from_json("[]"), Err(MoneyError::InvalidRateFile(_))));
+    }
+
     #[test]
     fn rate_convert() {
         let rate = ExchangeRate::new(USD, EUR, dec!(1.5)).unwrap();
@@ -109,6 +1473,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rate_inverse_and_compose() {
+        let eur_usd = ExchangeRate::new(EUR, USD, dec!(1.0850)).unwrap();
+        let usd_jpy = ExchangeRate::new(USD, JPY, dec!(151.37)).unwrap();
+        assert_eq!(eur_usd.rate(), dec!(1.0850));
+        assert_eq!(eur_usd.inverse().from, USD);
+        assert_eq!(eur_usd.inverse().rate().round_dp(6), dec!(0.921659));
+        assert_eq!(usd_jpy.inverse().inverse().rate().round_dp(20), dec!(151.37));
+
+        let eur_jpy = eur_usd.compose(&usd_jpy).unwrap();
+        assert_eq!(eur_jpy, ExchangeRate::new(EUR, JPY, dec!(164.236450)).unwrap());
+        assert_eq!(usd_jpy.compose(&eur_usd), Err(MoneyError::InvalidCurrency));
+        assert_eq!(eur_usd.compose(&eur_usd.inverse()), Err(MoneyError::InvalidCurrency));
+        let huge = ExchangeRate::new(USD, JPY, Decimal::MAX).unwrap();
+        assert_eq!(eur_usd.compose(&huge), Err(MoneyError::Overflow));
+    }
+
+    #[test]
+    fn rate_display_and_parse() {
+        let rate = ExchangeRate::new(EUR, USD, dec!(1.0850)).unwrap();
+        assert_eq!(rate.to_string(), "1 EUR = 1.0850 USD");
+        assert_eq!("EUR/USD 1.0850".parse::<ExchangeRate>(), Ok(rate.clone()));
+        assert_eq!(" 1  EUR = 1.0850 USD ".parse::<ExchangeRate>(), Ok(rate));
+
+        assert_eq!("EURUSD 1.0850".parse::<ExchangeRate>(), Err(MoneyError::InvalidAmountFormat));
+        assert_eq!("EUR/USD".parse::<ExchangeRate>(), Err(MoneyError::InvalidAmountFormat));
+        assert_eq!("2 EUR = 1.0850 USD".parse::<ExchangeRate>(), Err(MoneyError::InvalidAmountFormat));
+        assert!(matches!("EUR/USD abc".parse::<ExchangeRate>(), Err(MoneyError::InvalidAmountDecimal(_))));
+        assert_eq!("EUR/XYZ 1".parse::<ExchangeRate>(), Err(MoneyError::UnknownCurrency("XYZ".to_string())));
+        assert_eq!("EUR/EUR 1".parse::<ExchangeRate>(), Err(MoneyError::InvalidCurrency));
+        assert_eq!("EUR/USD -1.08".parse::<ExchangeRate>(), Err(MoneyError::InvalidExchangeRate));
+    }
+
+    #[test]
+    fn rate_new_errors_if_rate_is_not_positive() {
+        assert_eq!(ExchangeRate::new(EUR, USD, dec!(0)), Err(MoneyError::InvalidExchangeRate));
+        assert_eq!(ExchangeRate::new(EUR, USD, dec!(-1.08)), Err(MoneyError::InvalidExchangeRate));
+        assert_eq!(ExchangeRate::try_from_f64(EUR, USD, f64::NAN), Err(MoneyError::InvalidExchangeRate));
+        assert_eq!(ExchangeRate::try_from_f64(EUR, USD, f64::INFINITY), Err(MoneyError::InvalidExchangeRate));
+        assert_eq!(ExchangeRate::try_from_f64(EUR, USD, 0.0), Err(MoneyError::InvalidExchangeRate));
+        assert_eq!(ExchangeRate::try_from_f64(EUR, USD, 1e30), Err(MoneyError::Overflow));
+        assert_eq!(ExchangeRate::try_from_f64(EUR, USD, 1.085).unwrap().rate(), dec!(1.085));
+        assert!(serde_json::from_str::<ExchangeRate>(r#"{"from":"EUR","to":"USD","rate":"0"}"#).is_err());
+    }
+
     #[test]
     fn rate_new_errors_if_currencies_are_equal() {
         let rate = ExchangeRate::new(GBP, GBP, dec!(1.5));
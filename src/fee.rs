@@ -0,0 +1,362 @@
+use crate::{Amount, Money, MoneyError, RoundingPolicy};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// A threshold and rate in a tiered or marginal fee component.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeTier {
+    /// Upper bound of the tier in major units of the amount, inclusive. The last tier has no bound.
+    pub up_to: Option<Decimal>,
+    /// Rate as a decimal, e.g. `0.005` for 0.5%. Negative rates give discounts.
+    pub rate: Decimal,
+}
+
+impl FeeTier {
+    pub fn new(up_to: Option<Decimal>, rate: Decimal) -> FeeTier {
+        FeeTier { up_to, rate }
+    }
+}
+
+/// One part of a `FeeSchedule`. Each component adds a named item to the fee breakdown.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[serde(bound(serialize = "Money<A>: Serialize", deserialize = "Money<A>: Deserialize<'de>"))]
+pub enum FeeComponent<A = Decimal> {
+    /// A flat fee, such as the $0.30 of a card transaction.
+    Fixed { name: Option<String>, amount: Money<A> },
+    /// A percentage of the whole amount.
+    Percentage { name: Option<String>, rate: Decimal },
+    /// The whole amount is charged at the rate of the tier it falls into, like a volume discount.
+    Tiered { name: Option<String>, tiers: Vec<FeeTier> },
+    /// Each slice of the amount is charged at the rate of its own bracket, like progressive income tax.
+    Marginal { name: Option<String>, brackets: Vec<FeeTier> },
+}
+
+impl<A: Amount> FeeComponent<A> {
+    fn name(&self) -> String {
+        let (name, default) = match self {
+            FeeComponent::Fixed { name, .. } => (name, "fixed"),
+            FeeComponent::Percentage { name, .. } => (name, "percentage"),
+            FeeComponent::Tiered { name, .. } => (name, "tiered"),
+            FeeComponent::Marginal { name, .. } => (name, "marginal"),
+        };
+        name.clone().unwrap_or_else(|| default.to_string())
+    }
+
+    /// Returns the unrounded fee of this component on `amount`.
+    fn fee(&self, amount: &Money<A>) -> Result<Money<A>, MoneyError> {
+        let currency = *amount.currency();
+        let fee = match self {
+            FeeComponent::Fixed { amount: fee, .. } => {
+                if *fee.currency() != currency {
+                    return Err(MoneyError::CurrencyMismatch);
+                }
+                fee.amount().clone()
+            }
+            FeeComponent::Percentage { rate, .. } => amount.amount().mul_decimal(*rate),
+            FeeComponent::Tiered { tiers, .. } => {
+                check_tiers(tiers)?;
+                let tier = tiers
+                    .iter()
+                    .find(|tier| tier.up_to.is_none_or(|up_to| *amount.amount() <= A::from_decimal(up_to)))
+                    .expect("the last tier is unbounded");
+                amount.amount().mul_decimal(tier.rate)
+            }
+            FeeComponent::Marginal { brackets, .. } => {
+                check_tiers(brackets)?;
+                let mut fee = A::zero();
+                let mut lower = A::zero();
+                for bracket in brackets {
+                    let upper = match bracket.up_to {
+                        Some(up_to) => A::from_decimal(up_to).min(amount.amount().clone()),
+                        None => amount.amount().clone(),
+                    };
+                    if upper > lower {
+                        fee = fee + (upper.clone() - lower).mul_decimal(bracket.rate);
+                    }
+                    lower = upper.max(A::zero());
+                }
+                fee
+            }
+        };
+        Ok(Money::from_amount(fee, currency))
+    }
+}
+
+/// Tiers must have ascending bounds and end with an unbounded tier.
+fn check_tiers(tiers: &[FeeTier]) -> Result<(), MoneyError> {
+    let (last, bounded) = tiers.split_last().ok_or(MoneyError::InvalidFeeSchedule)?;
+    let bounds: Option<Vec<Decimal>> = bounded.iter().map(|tier| tier.up_to).collect();
+    match bounds {
+        Some(bounds) if last.up_to.is_none() && bounds.windows(2).all(|pair| pair[0] < pair[1]) => Ok(()),
+        _ => Err(MoneyError::InvalidFeeSchedule),
+    }
+}
+
+/// A single named amount in a fee breakdown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeItem<A = Decimal> {
+    pub name: String,
+    pub amount: Money<A>,
+}
+
+/// The itemized result of evaluating a `FeeSchedule`. The items always add up to `total`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeBreakdown<A = Decimal> {
+    pub items: Vec<FeeItem<A>>,
+    pub total: Money<A>,
+}
+
+/// A declarative fee made of fixed fees, percentages, tiers and marginal brackets, with an optional minimum and
+/// maximum for the total.
+///
+/// When the components add up to less than the minimum or more than the maximum, an adjustment item named
+/// "minimum" or "maximum" brings the total into range.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_money::{FeeComponent, FeeSchedule, Money, RoundingPolicy};
+/// use rusty_money::currencies::iso::USD;
+/// use rust_decimal::RoundingStrategy;
+/// use rust_decimal_macros::dec;
+///
+/// // "2.9% + $0.30"
+/// let card: FeeSchedule = FeeSchedule::new()
+///     .with(FeeComponent::Percentage { name: Some("processing".into()), rate: dec!(0.029) })
+///     .with(FeeComponent::Fixed { name: Some("transaction".into()), amount: Money::from_minor(30, USD) });
+///
+/// let cents = RoundingPolicy::ToCurrency(RoundingStrategy::MidpointAwayFromZero);
+/// let fee = card.evaluate(&Money::from_major(100, USD), cents).unwrap();
+/// assert_eq!(fee.items[0].amount, Money::from_minor(290, USD));
+/// assert_eq!(fee.total, Money::from_minor(320, USD));
+/// ```
+///
+/// Schedules can also be loaded with serde:
+///
+/// ```
+/// use rusty_money::{FeeSchedule, Money, RoundingPolicy};
+/// use rusty_money::currencies::iso::USD;
+///
+/// let json = r#"{
+///     "components": [{
+///         "type": "marginal",
+///         "name": "income tax",
+///         "brackets": [
+///             {"up_to": "11600", "rate": "0.10"},
+///             {"up_to": "47150", "rate": "0.12"},
+///             {"up_to": null, "rate": "0.22"}
+///         ]
+///     }]
+/// }"#;
+/// let brackets: FeeSchedule = serde_json::from_str(json).unwrap();
+/// let tax = brackets.evaluate(&Money::from_major(50_000, USD), RoundingPolicy::Unrounded).unwrap();
+/// assert_eq!(tax.total, Money::from_major(6_053, USD));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(serialize = "Money<A>: Serialize", deserialize = "Money<A>: Deserialize<'de>"))]
+pub struct FeeSchedule<A = Decimal> {
+    pub components: Vec<FeeComponent<A>>,
+    #[serde(default)]
+    pub minimum: Option<Money<A>>,
+    #[serde(default)]
+    pub maximum: Option<Money<A>>,
+}
+
+impl<A: Amount> FeeSchedule<A> {
+    /// Creates a schedule without components, which charges nothing.
+    pub fn new() -> FeeSchedule<A> {
+        FeeSchedule {
+            components: Vec::new(),
+            minimum: None,
+            maximum: None,
+        }
+    }
+
+    /// Returns the schedule with another component added.
+    pub fn with(mut self, component: FeeComponent<A>) -> FeeSchedule<A> {
+        self.components.push(component);
+        self
+    }
+
+    /// Returns the schedule with a minimum total fee.
+    pub fn with_minimum(mut self, minimum: Money<A>) -> FeeSchedule<A> {
+        self.minimum = Some(minimum);
+        self
+    }
+
+    /// Returns the schedule with a maximum total fee.
+    pub fn with_maximum(mut self, maximum: Money<A>) -> FeeSchedule<A> {
+        self.maximum = Some(maximum);
+        self
+    }
+
+    /// Evaluates the fee on an amount, rounding every item with `rounding`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::CurrencyMismatch` if a fixed fee, the minimum or the maximum is in a different currency
+    /// than `amount`, and `MoneyError::InvalidFeeSchedule` if the minimum is above the maximum or the tiers of a
+    /// component are empty, not ascending or do not end with an unbounded tier.
+    pub fn evaluate(&self, amount: &Money<A>, rounding: RoundingPolicy) -> Result<FeeBreakdown<A>, MoneyError> {
+        let currency = *amount.currency();
+        let mut items = Vec::with_capacity(self.components.len());
+        let mut total = A::zero();
+        for component in &self.components {
+            let fee = component.fee(amount)?.round_with(rounding);
+            total = total + fee.amount().clone();
+            items.push(FeeItem {
+                name: component.name(),
+                amount: fee,
+            });
+        }
+
+        for limit in [&self.minimum, &self.maximum].into_iter().flatten() {
+            if *limit.currency() != currency {
+                return Err(MoneyError::CurrencyMismatch);
+            }
+        }
+        if let (Some(minimum), Some(maximum)) = (&self.minimum, &self.maximum) {
+            if minimum.amount() > maximum.amount() {
+                return Err(MoneyError::InvalidFeeSchedule);
+            }
+        }
+
+        let adjusted_to = match (&self.minimum, &self.maximum) {
+            (Some(minimum), _) if total < *minimum.amount() => Some(("minimum", minimum)),
+            (_, Some(maximum)) if total > *maximum.amount() => Some(("maximum", maximum)),
+            _ => None,
+        };
+        if let Some((name, limit)) = adjusted_to {
+            let adjustment = limit.amount().clone() - total;
+            total = limit.amount().clone();
+            items.push(FeeItem {
+                name: name.to_string(),
+                amount: Money::from_amount(adjustment, currency),
+            });
+        }
+
+        Ok(FeeBreakdown {
+            items,
+            total: Money::from_amount(total, currency),
+        })
+    }
+}
+
+impl<A: Amount> Default for FeeSchedule<A> {
+    fn default() -> Self {
+        FeeSchedule::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::currencies::iso::{EUR, USD};
+    use crate::FixedPoint;
+    use rust_decimal::RoundingStrategy;
+    use rust_decimal_macros::dec;
+
+    const CENTS: RoundingPolicy = RoundingPolicy::ToCurrency(RoundingStrategy::MidpointAwayFromZero);
+
+    fn usd(minor: i64) -> Money {
+        Money::from_minor(minor, USD)
+    }
+
+    fn brokerage() -> FeeSchedule {
+        FeeSchedule::new()
+            .with(FeeComponent::Tiered {
+                name: None,
+                tiers: vec![
+                    FeeTier::new(Some(dec!(10_000)), dec!(0.005)),
+                    FeeTier::new(Some(dec!(100_000)), dec!(0.003)),
+                    FeeTier::new(None, dec!(0.001)),
+                ],
+            })
+            .with_minimum(usd(500))
+            .with_maximum(usd(20_000))
+    }
+
+    #[test]
+    fn fee_tiered_with_limits() {
+        let fee = brokerage().evaluate(&usd(500_000), CENTS).unwrap();
+        assert_eq!(fee.items[0].name, "tiered");
+        assert_eq!(fee.total, usd(2_500));
+
+        // Exactly on a bound stays in the lower tier
+        assert_eq!(brokerage().evaluate(&usd(1_000_000), CENTS).unwrap().total, usd(5_000));
+        assert_eq!(brokerage().evaluate(&usd(1_000_001), CENTS).unwrap().total, usd(3_000));
+
+        let small = brokerage().evaluate(&usd(10_000), CENTS).unwrap();
+        assert_eq!(small.items[1], FeeItem { name: "minimum".into(), amount: usd(450) });
+        assert_eq!(small.total, usd(500));
+
+        let large = brokerage().evaluate(&usd(1_000_000_000), CENTS).unwrap();
+        assert_eq!(large.items[1].amount, usd(-980_000));
+        assert_eq!(large.total, usd(20_000));
+    }
+
+    #[test]
+    fn fee_marginal_brackets() {
+        let brackets = FeeSchedule::new().with(FeeComponent::Marginal {
+            name: Some("tax".into()),
+            brackets: vec![FeeTier::new(Some(dec!(1_000)), dec!(0)), FeeTier::new(None, dec!(0.2))],
+        });
+        assert_eq!(brackets.evaluate(&usd(50_000), CENTS).unwrap().total, usd(0));
+        assert_eq!(brackets.evaluate(&usd(150_050), CENTS).unwrap().total, usd(10_010));
+        assert_eq!(brackets.evaluate(&usd(-500), CENTS).unwrap().total, usd(0));
+    }
+
+    #[test]
+    fn fee_items_are_rounded() {
+        let schedule = FeeSchedule::new()
+            .with(FeeComponent::Percentage { name: None, rate: dec!(0.0149) })
+            .with(FeeComponent::Percentage { name: Some("volume discount".into()), rate: dec!(-0.0025) });
+        let fee = schedule.evaluate(&usd(1_050), CENTS).unwrap();
+        assert_eq!(fee.items[0].amount, usd(16));
+        assert_eq!(fee.items[1].amount, usd(-3));
+        assert_eq!(fee.total, usd(13));
+
+        let exact = schedule.evaluate(&usd(1_050), RoundingPolicy::Unrounded).unwrap();
+        assert_eq!(exact.total, Money::from_decimal(dec!(0.13020), USD));
+    }
+
+    #[test]
+    fn fee_errors() {
+        let euro_fee = FeeSchedule::new().with(FeeComponent::Fixed { name: None, amount: Money::from_minor(25, EUR) });
+        assert_eq!(euro_fee.evaluate(&usd(100), CENTS), Err(MoneyError::CurrencyMismatch));
+        let euro_minimum = FeeSchedule::new().with_minimum(Money::from_minor(25, EUR));
+        assert_eq!(euro_minimum.evaluate(&usd(100), CENTS), Err(MoneyError::CurrencyMismatch));
+        let inverted_limits = FeeSchedule::new().with_minimum(usd(500)).with_maximum(usd(100));
+        assert_eq!(inverted_limits.evaluate(&usd(100), CENTS), Err(MoneyError::InvalidFeeSchedule));
+        assert!(MoneyError::InvalidFeeSchedule.to_string().contains("minimum must not exceed the maximum"));
+
+        let invalid_tiers = [
+            vec![],
+            vec![FeeTier::new(Some(dec!(10)), dec!(0.1))],
+            vec![
+                FeeTier::new(Some(dec!(10)), dec!(0.1)),
+                FeeTier::new(Some(dec!(5)), dec!(0.1)),
+                FeeTier::new(None, dec!(0)),
+            ],
+            vec![FeeTier::new(None, dec!(0.1)), FeeTier::new(None, dec!(0.1))],
+        ];
+        for tiers in invalid_tiers {
+            let schedule: FeeSchedule = FeeSchedule::new().with(FeeComponent::Tiered { name: None, tiers });
+            assert_eq!(schedule.evaluate(&usd(100), CENTS), Err(MoneyError::InvalidFeeSchedule));
+        }
+    }
+
+    #[test]
+    fn fee_schedule_serde() {
+        let schedule = brokerage().with(FeeComponent::Fixed { name: Some("exchange".into()), amount: usd(2) });
+        let json = serde_json::to_string(&schedule).unwrap();
+        let loaded: FeeSchedule = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, schedule);
+
+        let fixed: FeeSchedule<FixedPoint<2>> =
+            serde_json::from_str(r#"{"components": [{"type": "percentage", "rate": "0.01"}]}"#).unwrap();
+        let fee = fixed.evaluate(&Money::parse("12.34 USD").unwrap(), CENTS).unwrap();
+        assert_eq!(fee.total, Money::parse("0.12 USD").unwrap());
+    }
+}
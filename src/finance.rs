@@ -0,0 +1,677 @@
+//! Loan amortization schedules and time value of money calculations.
+//!
+//! Rates are periodic rates as decimals, e.g. `dec!(0.06) / dec!(12)` for 6% a year paid monthly. Payments fall at
+//! the end of each period. All cash-flow functions check that their amounts share one currency, returning
+//! `MoneyError::CurrencyMismatch` otherwise.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_money::{finance, Money, RoundingPolicy};
+//! use rusty_money::currencies::iso::USD;
+//! use rust_decimal::RoundingStrategy;
+//! use rust_decimal_macros::dec;
+//!
+//! let cents = RoundingPolicy::ToCurrency(RoundingStrategy::MidpointAwayFromZero);
+//! let mortgage = Money::from_major(200_000, USD);
+//! let payment = finance::pmt(dec!(0.06) / dec!(12), 360, &mortgage, cents).unwrap();
+//! assert_eq!(payment, Money::from_minor(119_910, USD));
+//! ```
+use crate::{Amount, Currency, Money, MoneyError, NaiveDate, RoundingPolicy};
+use rust_decimal::{Decimal, MathematicalOps};
+use std::borrow::Borrow;
+
+/// How a loan's principal is paid back over a schedule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Amortization {
+    /// Every installment has the same total payment, with a growing share of principal (an annuity loan).
+    LevelPayment,
+    /// Every installment repays the same principal, plus the interest on the remaining balance.
+    LevelPrincipal,
+    /// Installments pay interest only, and the full principal is repaid with the last one.
+    InterestOnly,
+}
+
+/// A single installment of an amortization schedule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Installment<A = Decimal> {
+    /// Position in the schedule, starting at 1.
+    pub number: u32,
+    /// Total amount paid, the sum of `principal` and `interest`.
+    pub payment: Money<A>,
+    /// Part of the payment that reduces the balance.
+    pub principal: Money<A>,
+    /// Part of the payment that covers interest for the period.
+    pub interest: Money<A>,
+    /// Balance left after this installment.
+    pub balance: Money<A>,
+}
+
+/// Builds an amortization schedule for a loan of `principal` at a periodic `rate` over `periods` installments.
+///
+/// Interest and payments are rounded with `rounding`, and the last installment absorbs every rounding difference so
+/// the balance ends at exactly zero.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_money::{finance, Money, RoundingPolicy};
+/// use rusty_money::finance::Amortization;
+/// use rusty_money::currencies::iso::USD;
+/// use rust_decimal::RoundingStrategy;
+/// use rust_decimal_macros::dec;
+///
+/// let cents = RoundingPolicy::ToCurrency(RoundingStrategy::MidpointAwayFromZero);
+/// let loan = Money::from_major(1_000, USD);
+/// let schedule = finance::schedule(&loan, dec!(0.01), 12, Amortization::LevelPayment, cents).unwrap();
+///
+/// assert_eq!(schedule[0].payment, Money::from_minor(8_885, USD));
+/// assert_eq!(schedule[11].payment, Money::from_minor(8_884, USD));
+/// assert!(schedule[11].balance.is_zero());
+/// ```
+///
+/// # Errors
+///
+/// Returns `MoneyError::InvalidPeriods` if `periods` is zero and `MoneyError::InvalidRatio` if `rate` is -100%
+/// or less.
+pub fn schedule<A: Amount>(
+    principal: &Money<A>,
+    rate: Decimal,
+    periods: u32,
+    amortization: Amortization,
+    rounding: RoundingPolicy,
+) -> Result<Vec<Installment<A>>, MoneyError> {
+    check_terms(rate, periods)?;
+    let currency = *principal.currency();
+
+    // Principal repaid in each installment, before the last one takes whatever balance is left
+    let level_principal = match amortization {
+        Amortization::LevelPrincipal => Some(principal.allocate_to(periods as i32)?),
+        _ => None,
+    };
+    let level_payment = match amortization {
+        Amortization::LevelPayment => Some(pmt(rate, periods, principal, rounding)?),
+        _ => None,
+    };
+
+    let mut balance = principal.clone();
+    let mut installments = Vec::with_capacity(periods as usize);
+    for number in 1..=periods {
+        let interest = Money::from_amount(balance.amount().mul_decimal(rate), currency).round_with(rounding);
+        let repaid = if number == periods {
+            balance.clone()
+        } else {
+            match (&level_payment, &level_principal) {
+                (Some(payment), _) => (payment.clone() - interest.clone())?,
+                (_, Some(shares)) => shares[number as usize - 1].clone(),
+                _ => Money::from_amount(A::zero(), currency),
+            }
+        };
+        balance = (balance - repaid.clone())?;
+        installments.push(Installment {
+            number,
+            payment: (repaid.clone() + interest.clone())?,
+            principal: repaid,
+            interest,
+            balance: balance.clone(),
+        });
+    }
+    Ok(installments)
+}
+
+/// Returns the level payment that repays `present_value` over `periods` at a periodic `rate`.
+///
+/// # Errors
+///
+/// Returns `MoneyError::InvalidPeriods` if `periods` is zero, `MoneyError::InvalidRatio` if `rate` is -100% or less
+/// and `MoneyError::Overflow` if the growth over the term cannot be represented.
+pub fn pmt<A: Amount>(
+    rate: Decimal,
+    periods: u32,
+    present_value: &Money<A>,
+    rounding: RoundingPolicy,
+) -> Result<Money<A>, MoneyError> {
+    check_terms(rate, periods)?;
+    let payment = if rate.is_zero() {
+        present_value.amount().clone() / A::from_decimal(Decimal::from(periods))
+    } else {
+        let growth = growth(rate, periods)?;
+        let factor = (rate * growth).checked_div(growth - Decimal::ONE).ok_or(MoneyError::Overflow)?;
+        present_value.amount().mul_decimal(factor)
+    };
+    Ok(Money::from_amount(payment, *present_value.currency()).round_with(rounding))
+}
+
+/// Returns the present value of `periods` level payments of `payment` at a periodic `rate`.
+///
+/// # Errors
+///
+/// Returns `MoneyError::InvalidPeriods` if `periods` is zero, `MoneyError::InvalidRatio` if `rate` is -100% or less
+/// and `MoneyError::Overflow` if the growth over the term cannot be represented.
+pub fn pv<A: Amount>(
+    rate: Decimal,
+    periods: u32,
+    payment: &Money<A>,
+    rounding: RoundingPolicy,
+) -> Result<Money<A>, MoneyError> {
+    check_terms(rate, periods)?;
+    let factor = if rate.is_zero() {
+        Decimal::from(periods)
+    } else {
+        let growth = growth(rate, periods)?;
+        (growth - Decimal::ONE).checked_div(rate * growth).ok_or(MoneyError::Overflow)?
+    };
+    Ok(Money::from_amount(payment.amount().mul_decimal(factor), *payment.currency()).round_with(rounding))
+}
+
+/// Returns the future value of `present_value` plus `periods` level payments of `payment` at a periodic `rate`.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_money::{finance, Money, RoundingPolicy};
+/// use rusty_money::currencies::iso::USD;
+/// use rust_decimal::RoundingStrategy;
+/// use rust_decimal_macros::dec;
+///
+/// // Saving $100 a month at 1% a month for a year
+/// let cents = RoundingPolicy::ToCurrency(RoundingStrategy::MidpointAwayFromZero);
+/// let saved = finance::fv(dec!(0.01), 12, &Money::from_major(0, USD), &Money::from_major(100, USD), cents);
+/// assert_eq!(saved.unwrap(), Money::from_minor(126_825, USD));
+/// ```
+///
+/// # Errors
+///
+/// Returns `MoneyError::CurrencyMismatch` if the amounts have different currencies, `MoneyError::InvalidPeriods` if
+/// `periods` is zero, `MoneyError::InvalidRatio` if `rate` is -100% or less and `MoneyError::Overflow` if the growth
+/// over the term cannot be represented.
+pub fn fv<A: Amount>(
+    rate: Decimal,
+    periods: u32,
+    present_value: &Money<A>,
+    payment: &Money<A>,
+    rounding: RoundingPolicy,
+) -> Result<Money<A>, MoneyError> {
+    if present_value.currency() != payment.currency() {
+        return Err(MoneyError::CurrencyMismatch);
+    }
+    check_terms(rate, periods)?;
+    let (growth, annuity) = if rate.is_zero() {
+        (Decimal::ONE, Decimal::from(periods))
+    } else {
+        let growth = growth(rate, periods)?;
+        (growth, (growth - Decimal::ONE) / rate)
+    };
+    let value = present_value.amount().mul_decimal(growth) + payment.amount().mul_decimal(annuity);
+    Ok(Money::from_amount(value, *payment.currency()).round_with(rounding))
+}
+
+/// Returns the net present value of cash flows at a periodic `rate`.
+///
+/// The first flow happens now and is not discounted, the next one a period later and so on. Spreadsheet `NPV`
+/// functions discount the first flow too, so they match `npv` with the initial flow added separately.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_money::{finance, Money, RoundingPolicy};
+/// use rusty_money::currencies::iso::USD;
+/// use rust_decimal::RoundingStrategy;
+/// use rust_decimal_macros::dec;
+///
+/// let flows = [-1_000, 500, 500, 500].map(|major| Money::from_major(major, USD));
+/// let cents = RoundingPolicy::ToCurrency(RoundingStrategy::MidpointAwayFromZero);
+/// assert_eq!(finance::npv(dec!(0.1), &flows, cents).unwrap(), Money::from_minor(24_343, USD));
+/// ```
+///
+/// # Errors
+///
+/// Returns `MoneyError::EmptySum` without flows, `MoneyError::CurrencyMismatch` for mixed currencies,
+/// `MoneyError::InvalidRatio` if `rate` is -100% or less and `MoneyError::Overflow` if a discount factor cannot be
+/// represented.
+pub fn npv<A, I>(rate: Decimal, flows: I, rounding: RoundingPolicy) -> Result<Money<A>, MoneyError>
+where
+    A: Amount,
+    I: IntoIterator,
+    I::Item: Borrow<Money<A>>,
+{
+    let dated = flows.into_iter().enumerate().map(|(period, flow)| (Decimal::from(period), flow));
+    discounted_sum(rate, dated, rounding)
+}
+
+/// Returns the net present value of dated cash flows at an annual `rate`.
+///
+/// Flows are discounted to the date of the first flow by the years between them, counted as ACT/365F.
+///
+/// # Errors
+///
+/// Returns `MoneyError::EmptySum` without flows, `MoneyError::CurrencyMismatch` for mixed currencies,
+/// `MoneyError::InvalidDateRange` if a flow is dated before the first one, `MoneyError::InvalidRatio` if `rate` is
+/// -100% or less and `MoneyError::Overflow` if a discount factor cannot be represented.
+pub fn xnpv<A, I, M>(rate: Decimal, flows: I, rounding: RoundingPolicy) -> Result<Money<A>, MoneyError>
+where
+    A: Amount,
+    I: IntoIterator<Item = (NaiveDate, M)>,
+    M: Borrow<Money<A>>,
+{
+    let flows: Vec<(NaiveDate, M)> = flows.into_iter().collect();
+    let years = year_offsets(flows.iter().map(|(date, _)| *date))?;
+    discounted_sum(rate, years.into_iter().zip(flows.into_iter().map(|(_, flow)| flow)), rounding)
+}
+
+/// Returns the internal rate of return of periodic cash flows: the rate at which their `npv` is zero.
+///
+/// Rates are scanned from -99% in steps of 5% up to 100%, 25% up to 1000% and then doubling, until their discount
+/// factors no longer fit into a `Decimal`; the first change of sign of the net present value is solved. Two rates
+/// between the same two steps are found only if the value at a step next to them is closer to zero than at the
+/// steps on either side of it; otherwise both can be missed and a higher rate returned. The result is accurate to
+/// at least twelve decimal places, or to twelve significant digits for rates above 100%.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_money::{finance, Money};
+/// use rusty_money::currencies::iso::USD;
+/// use rust_decimal_macros::dec;
+///
+/// let flows = [-1_000, 500, 500, 500].map(|major| Money::from_major(major, USD));
+/// assert_eq!(finance::irr(&flows).unwrap().round_dp(6), dec!(0.233752));
+/// ```
+///
+/// # Errors
+///
+/// Returns `MoneyError::EmptySum` without flows, `MoneyError::CurrencyMismatch` for mixed currencies and
+/// `MoneyError::NoSolution` if the search finds no rate that sets the net present value to zero.
+pub fn irr<A, I>(flows: I) -> Result<Decimal, MoneyError>
+where
+    A: Amount,
+    I: IntoIterator,
+    I::Item: Borrow<Money<A>>,
+{
+    let mut currency = None;
+    let mut points = Vec::new();
+    for (period, flow) in flows.into_iter().enumerate() {
+        points.push((Decimal::from(period), flow_value(&mut currency, flow.borrow())?));
+    }
+    solve_rate(&points)
+}
+
+/// Returns the annual internal rate of return of dated cash flows: the rate at which their `xnpv` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_money::{finance, Money, NaiveDate};
+/// use rusty_money::currencies::iso::USD;
+/// use rust_decimal_macros::dec;
+///
+/// let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+/// let flows = vec![
+///     (date(2008, 1, 1), Money::from_major(-10_000, USD)),
+///     (date(2008, 3, 1), Money::from_major(2_750, USD)),
+///     (date(2008, 10, 30), Money::from_major(4_250, USD)),
+///     (date(2009, 2, 15), Money::from_major(3_250, USD)),
+///     (date(2009, 4, 1), Money::from_major(2_750, USD)),
+/// ];
+/// assert_eq!(finance::xirr(flows).unwrap().round_dp(4), dec!(0.3734));
+/// ```
+///
+/// # Errors
+///
+/// Returns `MoneyError::EmptySum` without flows, `MoneyError::CurrencyMismatch` for mixed currencies,
+/// `MoneyError::InvalidDateRange` if a flow is dated before the first one and `MoneyError::NoSolution` if the search
+/// finds no rate that sets the net present value to zero.
+pub fn xirr<A, I, M>(flows: I) -> Result<Decimal, MoneyError>
+where
+    A: Amount,
+    I: IntoIterator<Item = (NaiveDate, M)>,
+    M: Borrow<Money<A>>,
+{
+    let flows: Vec<(NaiveDate, M)> = flows.into_iter().collect();
+    let years = year_offsets(flows.iter().map(|(date, _)| *date))?;
+    let mut currency = None;
+    let mut points = Vec::with_capacity(flows.len());
+    for (offset, (_, flow)) in years.into_iter().zip(&flows) {
+        points.push((offset, flow_value(&mut currency, flow.borrow())?));
+    }
+    solve_rate(&points)
+}
+
+fn check_terms(rate: Decimal, periods: u32) -> Result<(), MoneyError> {
+    if periods == 0 {
+        return Err(MoneyError::InvalidPeriods);
+    }
+    if rate <= -Decimal::ONE {
+        return Err(MoneyError::InvalidRatio);
+    }
+    Ok(())
+}
+
+/// `(1 + rate)^periods`
+fn growth(rate: Decimal, periods: u32) -> Result<Decimal, MoneyError> {
+    (Decimal::ONE + rate).checked_powu(u64::from(periods)).ok_or(MoneyError::Overflow)
+}
+
+/// `(1 + rate)^-years`, or None if it cannot be represented.
+fn discount_factor(rate: Decimal, years: Decimal) -> Option<Decimal> {
+    Decimal::ONE.checked_div((Decimal::ONE + rate).checked_powd(years)?)
+}
+
+/// Years from the first date to each date, counted as ACT/365F.
+fn year_offsets(dates: impl Iterator<Item = NaiveDate>) -> Result<Vec<Decimal>, MoneyError> {
+    let mut first = None;
+    let mut offsets = Vec::new();
+    for date in dates {
+        let first = *first.get_or_insert(date);
+        offsets.push(crate::DayCount::Act365Fixed.year_fraction(first, date)?);
+    }
+    Ok(offsets)
+}
+
+fn discounted_sum<A, M>(
+    rate: Decimal,
+    flows: impl Iterator<Item = (Decimal, M)>,
+    rounding: RoundingPolicy,
+) -> Result<Money<A>, MoneyError>
+where
+    A: Amount,
+    M: Borrow<Money<A>>,
+{
+    if rate <= -Decimal::ONE {
+        return Err(MoneyError::InvalidRatio);
+    }
+    let mut currency: Option<Currency> = None;
+    let mut total = A::zero();
+    for (years, flow) in flows {
+        let flow = flow.borrow();
+        match currency {
+            Some(expected) if expected != *flow.currency() => return Err(MoneyError::CurrencyMismatch),
+            _ => currency = Some(*flow.currency()),
+        }
+        let factor = discount_factor(rate, years).ok_or(MoneyError::Overflow)?;
+        total = total + flow.amount().mul_decimal(factor);
+    }
+    let currency = currency.ok_or(MoneyError::EmptySum)?;
+    Ok(Money::from_amount(total, currency).round_with(rounding))
+}
+
+/// Checks a flow's currency against the previous ones and returns its amount as a `Decimal`.
+fn flow_value<A: Amount>(currency: &mut Option<Currency>, flow: &Money<A>) -> Result<Decimal, MoneyError> {
+    match currency {
+        Some(expected) if *expected != *flow.currency() => return Err(MoneyError::CurrencyMismatch),
+        _ => *currency = Some(*flow.currency()),
+    }
+    flow.amount().to_decimal().ok_or(MoneyError::Overflow)
+}
+
+/// Finds the lowest rate the scan reaches at which `points` of `(years, amount)` have a net present value of zero.
+///
+/// The net present value is not monotonic in general, so candidate rates are scanned from low to high for the first
+/// sign change, or a dip towards zero that `turning_point` shows to cross it, which is then narrowed down by
+/// bisection. Bisection is slower than Newton's method but cannot
+/// diverge or jump to a different root.
+fn solve_rate(points: &[(Decimal, Decimal)]) -> Result<Decimal, MoneyError> {
+    if points.is_empty() {
+        return Err(MoneyError::EmptySum);
+    }
+    let npv = |rate: Decimal| -> Option<Decimal> {
+        points.iter().try_fold(Decimal::ZERO, |total, (years, amount)| {
+            total.checked_add((*amount).checked_mul(discount_factor(rate, *years)?)?)
+        })
+    };
+
+    let mut previous: Vec<(Decimal, Decimal)> = Vec::with_capacity(2);
+    for rate in scan_rates() {
+        let value = match npv(rate) {
+            Some(value) => value,
+            // Discount factors only grow out of range at the low end, or for huge rates at the high end
+            None if rate.is_sign_negative() => continue,
+            None => break,
+        };
+        if value.is_zero() {
+            return Ok(rate);
+        }
+        if let Some(&(low, low_value)) = previous.last() {
+            if low_value.is_sign_negative() != value.is_sign_negative() {
+                return bisect(&npv, low, low_value, rate);
+            }
+        }
+        // Two roots between three rates show as a dip of the value towards zero without a change of sign
+        if let [(start, start_value), (_, middle_value)] = previous[..] {
+            if middle_value.abs() < start_value.abs() && middle_value.abs() < value.abs() {
+                let (turn, turn_value) = turning_point(&npv, start, start_value, rate)?;
+                if turn_value.is_zero() {
+                    return Ok(turn);
+                }
+                if turn_value.is_sign_negative() != start_value.is_sign_negative() {
+                    return bisect(&npv, start, start_value, turn);
+                }
+            }
+            previous.remove(0);
+        }
+        previous.push((rate, value));
+    }
+    Err(MoneyError::NoSolution)
+}
+
+/// Rates searched for a change of sign of the net present value: every 5% up to 100%, every 25% up to 1000%, then
+/// doubling for as long as the rate fits into a `Decimal`.
+fn scan_rates() -> impl Iterator<Item = Decimal> {
+    let near = (-19..=20).map(|step| Decimal::new(step * 5, 2));
+    let far = (5..=40).map(|step| Decimal::new(step * 25, 2));
+    let doubling = std::iter::successors(Some(Decimal::from(20)), |rate| Decimal::checked_mul(*rate, Decimal::TWO));
+    std::iter::once(Decimal::new(-99, 2)).chain(near).chain(far).chain(doubling)
+}
+
+/// Returns the rate between `low` and `high` where the net present value comes closest to, or furthest past, zero
+/// from the side of `low_value`.
+fn turning_point(
+    npv: &impl Fn(Decimal) -> Option<Decimal>,
+    mut low: Decimal,
+    low_value: Decimal,
+    mut high: Decimal,
+) -> Result<(Decimal, Decimal), MoneyError> {
+    let towards_zero = |value: Decimal| if low_value.is_sign_negative() { -value } else { value };
+    let tolerance = Decimal::new(1, 14);
+    while high - low > tolerance {
+        let third = (high - low) / Decimal::from(3);
+        let left = npv(low + third).ok_or(MoneyError::Overflow)?;
+        let right = npv(high - third).ok_or(MoneyError::Overflow)?;
+        if towards_zero(left) < towards_zero(right) {
+            high -= third;
+        } else {
+            low += third;
+        }
+    }
+    let middle = (low + high) / Decimal::TWO;
+    Ok((middle, npv(middle).ok_or(MoneyError::Overflow)?))
+}
+
+fn bisect(
+    npv: &impl Fn(Decimal) -> Option<Decimal>,
+    mut low: Decimal,
+    low_value: Decimal,
+    mut high: Decimal,
+) -> Result<Decimal, MoneyError> {
+    let tolerance = Decimal::new(1, 14);
+    let low_negative = low_value.is_sign_negative();
+    while high - low > tolerance {
+        let middle = (low + high) / Decimal::TWO;
+        let value = npv(middle).ok_or(MoneyError::Overflow)?;
+        if value.is_zero() {
+            return Ok(middle);
+        }
+        if value.is_sign_negative() == low_negative {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    Ok(((low + high) / Decimal::TWO).round_dp(13))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::currencies::iso::{EUR, JPY, USD};
+    use crate::{FixedPoint, MoneyIterator};
+    use rust_decimal::RoundingStrategy;
+    use rust_decimal_macros::dec;
+
+    const CENTS: RoundingPolicy = RoundingPolicy::ToCurrency(RoundingStrategy::MidpointAwayFromZero);
+
+    fn usd(minor: i64) -> Money {
+        Money::from_minor(minor, USD)
+    }
+
+    fn assert_reconciles(schedule: &[Installment], principal: &Money) {
+        let repaid = schedule.iter().map(|i| &i.principal).try_sum().unwrap();
+        assert_eq!(&repaid, principal);
+        assert!(schedule.last().unwrap().balance.is_zero());
+        for installment in schedule {
+            assert_eq!(
+                installment.payment,
+                (installment.principal.clone() + installment.interest.clone()).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn schedule_level_payment() {
+        let principal = usd(100_000);
+        let schedule = schedule(&principal, dec!(0.01), 12, Amortization::LevelPayment, CENTS).unwrap();
+        assert_eq!(schedule.len(), 12);
+        assert_eq!(schedule[0].interest, usd(1_000));
+        assert_eq!(schedule[0].principal, usd(7_885));
+        assert_eq!(schedule[0].balance, usd(92_115));
+        assert!(schedule[..11].iter().all(|i| i.payment == usd(8_885)));
+        assert_eq!(schedule[11].payment, usd(8_884));
+        assert_reconciles(&schedule, &principal);
+    }
+
+    #[test]
+    fn schedule_level_principal() {
+        let principal = usd(100_000);
+        let schedule = schedule(&principal, dec!(0.01), 3, Amortization::LevelPrincipal, CENTS).unwrap();
+        let principals: Vec<Money> = schedule.iter().map(|i| i.principal.clone()).collect();
+        assert_eq!(principals, vec![usd(33_334), usd(33_333), usd(33_333)]);
+        let interest: Vec<Money> = schedule.iter().map(|i| i.interest.clone()).collect();
+        assert_eq!(interest, vec![usd(1_000), usd(667), usd(333)]);
+        assert_reconciles(&schedule, &principal);
+    }
+
+    #[test]
+    fn schedule_interest_only() {
+        let principal = usd(100_000);
+        let schedule = schedule(&principal, dec!(0.01), 3, Amortization::InterestOnly, CENTS).unwrap();
+        assert_eq!(schedule[0].payment, usd(1_000));
+        assert_eq!(schedule[1].balance, principal);
+        assert_eq!(schedule[2].payment, usd(101_000));
+        assert_reconciles(&schedule, &principal);
+    }
+
+    #[test]
+    fn schedule_edge_cases() {
+        let principal = usd(1_000);
+        let free = schedule(&principal, dec!(0), 3, Amortization::LevelPayment, CENTS).unwrap();
+        let payments: Vec<Money> = free.iter().map(|i| i.payment.clone()).collect();
+        assert_eq!(payments, vec![usd(333), usd(333), usd(334)]);
+        assert_reconciles(&free, &principal);
+
+        let yen = Money::from_major(1_000_000, JPY);
+        let schedule_jpy = schedule(&yen, dec!(0.005), 7, Amortization::LevelPayment, CENTS).unwrap();
+        assert!(schedule_jpy.iter().all(|i| i.payment.amount().fract().is_zero()));
+
+        assert_eq!(
+            schedule(&principal, dec!(0.01), 0, Amortization::LevelPayment, CENTS),
+            Err(MoneyError::InvalidPeriods)
+        );
+        assert_eq!(
+            schedule(&principal, dec!(-1), 3, Amortization::LevelPayment, CENTS),
+            Err(MoneyError::InvalidRatio)
+        );
+    }
+
+    #[test]
+    fn schedule_fixed_point() {
+        let principal = Money::<FixedPoint<2>>::parse("2500.00 EUR").unwrap();
+        let schedule = schedule(&principal, dec!(0.0075), 24, Amortization::LevelPayment, CENTS).unwrap();
+        assert_eq!(schedule[0].payment, Money::parse("114.21 EUR").unwrap());
+        let repaid = schedule.iter().map(|i| &i.principal).try_sum().unwrap();
+        assert_eq!(repaid, principal);
+    }
+
+    #[test]
+    fn annuity_functions() {
+        assert_eq!(pmt(dec!(0.005), 360, &usd(20_000_000), CENTS), Ok(usd(119_910)));
+        assert_eq!(pmt(dec!(0), 4, &usd(1_000), CENTS), Ok(usd(250)));
+        assert_eq!(pv(dec!(0.01), 12, &usd(8_885), CENTS), Ok(usd(100_001)));
+        assert_eq!(pv(dec!(0), 12, &usd(100), CENTS), Ok(usd(1_200)));
+        assert_eq!(fv(dec!(0.05), 10, &usd(100_000), &usd(0), CENTS), Ok(usd(162_889)));
+        assert_eq!(fv(dec!(0), 10, &usd(100), &usd(10), CENTS), Ok(usd(200)));
+        assert_eq!(
+            fv(dec!(0.05), 10, &usd(100), &Money::from_minor(10, EUR), CENTS),
+            Err(MoneyError::CurrencyMismatch)
+        );
+    }
+
+    #[test]
+    fn net_present_value() {
+        let flows = [usd(-100_000), usd(50_000), usd(50_000), usd(50_000)];
+        assert_eq!(npv(dec!(0.1), &flows, CENTS), Ok(usd(24_343)));
+        assert_eq!(npv(dec!(0), &flows, CENTS), Ok(usd(50_000)));
+        assert_eq!(npv(dec!(0.1), Vec::<Money>::new(), CENTS), Err(MoneyError::EmptySum));
+        assert_eq!(
+            npv(dec!(0.1), [usd(1), Money::from_minor(1, EUR)], CENTS),
+            Err(MoneyError::CurrencyMismatch)
+        );
+
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let dated = [(date(2024, 1, 1), usd(-100_000)), (date(2025, 1, 1), usd(110_000))];
+        // 366 days is slightly more than a year on ACT/365F
+        assert_eq!(xnpv(dec!(0.1), dated, CENTS), Ok(usd(-26)));
+        let backwards = [(date(2024, 1, 1), usd(-100_000)), (date(2023, 1, 1), usd(110_000))];
+        assert_eq!(xnpv(dec!(0.1), backwards, CENTS), Err(MoneyError::InvalidDateRange));
+    }
+
+    #[test]
+    fn internal_rate_of_return() {
+        let flows = [usd(-100_000), usd(50_000), usd(50_000), usd(50_000)];
+        let rate = irr(&flows).unwrap();
+        assert_eq!(rate.round_dp(10), dec!(0.2337519285));
+        assert!(npv(rate, &flows, CENTS).unwrap().is_zero());
+
+        // Losing money gives a negative rate
+        assert_eq!(irr([usd(-1_000), usd(900)]).unwrap().round_dp(10), dec!(-0.1));
+        assert_eq!(irr([usd(-1_000), usd(1_000)]), Ok(dec!(0)));
+
+        // Rates beyond 1000% are found as well, however large
+        assert_eq!(irr([usd(-10_000), usd(200_000)]), Ok(dec!(19)));
+        assert_eq!(irr([usd(-1), usd(100_000_000)]).unwrap().round_dp(10), dec!(99_999_999));
+        assert_eq!(irr([usd(-1), usd(10_000_000_000_000)]).unwrap().round_dp(2), dec!(9_999_999_999_999));
+        // Of two rates 2% apart the lower one is returned
+        assert_eq!(irr([usd(-10_000), usd(22_400), usd(-12_543)]).unwrap().round_dp(10), dec!(0.11));
+
+        assert_eq!(irr([usd(1_000), usd(1_000)]), Err(MoneyError::NoSolution));
+        assert_eq!(irr(Vec::<Money>::new()), Err(MoneyError::EmptySum));
+        assert_eq!(irr([usd(-1), Money::from_minor(2, EUR)]), Err(MoneyError::CurrencyMismatch));
+    }
+
+    #[test]
+    fn extended_internal_rate_of_return() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let flows = vec![
+            (date(2008, 1, 1), usd(-1_000_000)),
+            (date(2008, 3, 1), usd(275_000)),
+            (date(2008, 10, 30), usd(425_000)),
+            (date(2009, 2, 15), usd(325_000)),
+            (date(2009, 4, 1), usd(275_000)),
+        ];
+        let rate = xirr(flows.iter().map(|(d, m)| (*d, m))).unwrap();
+        assert_eq!(rate.round_dp(9), dec!(0.373362534));
+        assert!(xnpv(rate, flows, CENTS).unwrap().is_zero());
+
+        let exact_year = [(date(2023, 1, 1), usd(-100_000)), (date(2024, 1, 1), usd(110_000))];
+        assert_eq!(xirr(exact_year).unwrap().round_dp(10), dec!(0.1));
+    }
+}
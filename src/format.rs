@@ -1,4 +1,5 @@
-use crate::Money; // Removed Round from here
+use crate::money::with_exponent_digits;
+use crate::{Amount, Denomination, Money}; // Removed Round from here
 use std::cmp::Ordering;
 
 /// Converts Money objects into human readable strings.
@@ -54,17 +55,27 @@ impl Formatter {
     /// This method will panic if the input decimal representation contains more
     /// than one decimal point (exponent separator), which should never occur with
     /// a valid `Decimal` value from a `Money` object.
-    pub fn money(money: &Money, params: Params) -> String {
-        let mut decimal = *money.amount();
+    pub fn money<A: Amount>(money: &Money<A>, params: Params) -> String {
+        let mut amount = money.amount().clone();
 
-        // Round the decimal using MidpointNearestEven (equivalent to the old HalfEven)
-        if let Some(x) = params.rounding {
-             // Use the imported RoundingStrategy enum
-            decimal = *money.round(x, rust_decimal::RoundingStrategy::MidpointNearestEven).amount();
+        // Express the amount in the requested denomination, e.g. ETH in gwei
+        if let Some(denomination) = &params.denomination {
+            amount = amount * A::pow10(denomination.exponent);
         }
 
+        // Round the amount using MidpointNearestEven (equivalent to the old HalfEven)
+        let raw_amount = match params.rounding {
+            Some(x) => {
+                 // Use the imported RoundingStrategy enum
+                let rounded = amount.round_dp(x, rust_decimal::RoundingStrategy::MidpointNearestEven);
+                // Show exactly `x` digits, whatever scale the backend prints
+                with_exponent_digits(&format!("{}", rounded), x)
+            }
+            None => format!("{}", amount),
+        };
+
         // Format the Amount String
-        let amount = Formatter::amount(&format!("{}", decimal), &params);
+        let amount = Formatter::amount(&raw_amount, &params);
 
         // Position values in the Output String
         let mut result = String::new();
@@ -181,6 +192,13 @@ pub struct Params {
     
     /// The currency's ISO code (e.g., "USD", "EUR", "GBP")
     pub code: Option<&'static str>,
+
+    /// The denomination to display the amount in (e.g., satoshi for BTC, gwei for ETH)
+    ///
+    /// When `Some`, the amount is scaled to the denomination before rounding, so
+    /// 0.000021 ETH in gwei is displayed as 21,000. Use `find_denomination` to look one up,
+    /// and `code` or `symbol` to display its name.
+    pub denomination: Option<Denomination>,
 }
 
 impl Default for Params {
@@ -194,6 +212,7 @@ impl Default for Params {
             rounding: None,
             symbol: None,
             code: None,
+            denomination: None,
         }
     }
 }
@@ -341,4 +360,45 @@ mod tests {
             Formatter::money(&money, params)
         );
     }
+
+    #[test]
+    fn format_in_denomination() {
+        use crate::currencies::crypto::ETH;
+        use crate::find_denomination;
+
+        let money = Money::from_denomination(21_000, "gwei", ETH).unwrap();
+        let params = Params {
+            code: Some("gwei"),
+            denomination: find_denomination(&ETH, "gwei"),
+            rounding: Some(0),
+            positions: vec![Position::Amount, Position::Space, Position::Code],
+            ..Default::default()
+        };
+        assert_eq!("21,000 gwei", Formatter::money(&money, params));
+
+        let money = Money::from_denomination(21, "gwei", ETH).unwrap();
+        let params = Params {
+            denomination: find_denomination(&ETH, "wei"),
+            rounding: Some(0),
+            ..Default::default()
+        };
+        assert_eq!("21,000,000,000", Formatter::money(&money, params));
+    }
+
+    #[test]
+    fn format_fixed_point_amounts() {
+        let money = Money::from_amount(crate::FixedPoint::<4>::from_units(-12_345_678), USD);
+
+        let params = Params {
+            symbol: Some("$"),
+            ..Default::default()
+        };
+        assert_eq!("-$1,234.5678", Formatter::money(&money, params));
+
+        let params = Params {
+            rounding: Some(2),
+            ..Default::default()
+        };
+        assert_eq!("-1,234.57", Formatter::money(&money, params));
+    }
 }
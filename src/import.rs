@@ -0,0 +1,286 @@
+use crate::currencies::iso::EUR;
+use crate::{find_currency, Currency, ExchangeRate, MoneyError, NaiveDate};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// A file format that exchange rates can be imported from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateFormat {
+    /// The ECB euro foreign exchange reference rates as XML, daily or historical (`eurofxref-daily.xml`).
+    EcbXml,
+    /// The ECB euro foreign exchange reference rates as CSV, daily or historical (`eurofxref.csv`).
+    EcbCsv,
+    /// A JSON snapshot as published by openexchangerates.org (`latest.json` or `historical/*.json`).
+    OpenExchangeRates,
+}
+
+/// What an import does with currency codes it does not know.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnknownCurrencies {
+    /// The import fails with `MoneyError::UnknownCurrency`.
+    Fail,
+    /// Rates for unknown currencies are left out.
+    Skip,
+}
+
+/// An exchange rate read from a file, with the date it was published for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedRate {
+    pub date: NaiveDate,
+    pub rate: ExchangeRate,
+}
+
+impl RateFormat {
+    /// Parses the rates in a file of this format. Values marked as unavailable are left out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::{ExchangeRate, NaiveDate, RateFormat, UnknownCurrencies};
+    /// use rusty_money::currencies::iso::{EUR, USD};
+    /// use rust_decimal_macros::dec;
+    ///
+    /// let csv = "Date, USD, \n14 June 2024, 1.0686, \n";
+    /// let rates = RateFormat::EcbCsv.parse(csv, UnknownCurrencies::Fail).unwrap();
+    /// assert_eq!(rates[0].date, NaiveDate::from_ymd_opt(2024, 6, 14).unwrap());
+    /// assert_eq!(rates[0].rate, ExchangeRate::new(EUR, USD, dec!(1.0686)).unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::InvalidRateFile` if the contents are not valid for the format, and
+    /// `MoneyError::UnknownCurrency` for a currency code that is not known, unless unknown currencies are skipped.
+    pub fn parse(&self, contents: &str, unknown: UnknownCurrencies) -> Result<Vec<ImportedRate>, MoneyError> {
+        let parser = Parser { unknown };
+        match self {
+            RateFormat::EcbXml => parser.ecb_xml(contents),
+            RateFormat::EcbCsv => parser.ecb_csv(contents),
+            RateFormat::OpenExchangeRates => parser.open_exchange_rates(contents),
+        }
+    }
+}
+
+struct Parser {
+    unknown: UnknownCurrencies,
+}
+
+impl Parser {
+    fn ecb_xml(&self, xml: &str) -> Result<Vec<ImportedRate>, MoneyError> {
+        let mut rates = Vec::new();
+        let mut date = None;
+        for element in xml.split("<Cube").skip(1) {
+            let tag = element.split('>').next().unwrap_or_default();
+            if let Some(time) = attribute(tag, "time") {
+                date = Some(parse_date(time)?);
+            }
+            let (Some(code), Some(rate)) = (attribute(tag, "currency"), attribute(tag, "rate")) else {
+                continue;
+            };
+            let date = date.ok_or_else(|| invalid(format!("rate for {} outside of a dated Cube", code)))?;
+            if let Some(rate) = self.rate(EUR, code, rate)? {
+                rates.push(ImportedRate { date, rate });
+            }
+        }
+        Ok(rates)
+    }
+
+    fn ecb_csv(&self, csv: &str) -> Result<Vec<ImportedRate>, MoneyError> {
+        let mut lines = csv.lines().filter(|line| !line.trim().is_empty());
+        let header = lines.next().ok_or_else(|| invalid("empty file".to_string()))?;
+        let codes: Vec<&str> = header.split(',').map(str::trim).skip(1).collect();
+
+        let mut rates = Vec::new();
+        for line in lines {
+            let mut fields = line.split(',').map(str::trim);
+            let date = parse_date(fields.next().unwrap_or_default())?;
+            for (code, value) in codes.iter().zip(fields) {
+                if code.is_empty() || value.is_empty() || value == "N/A" {
+                    continue;
+                }
+                if let Some(rate) = self.rate(EUR, code, value)? {
+                    rates.push(ImportedRate { date, rate });
+                }
+            }
+        }
+        Ok(rates)
+    }
+
+    fn open_exchange_rates(&self, json: &str) -> Result<Vec<ImportedRate>, MoneyError> {
+        #[derive(Deserialize)]
+        struct Snapshot {
+            timestamp: i64,
+            base: String,
+            rates: BTreeMap<String, Decimal>,
+        }
+
+        let snapshot: Snapshot = serde_json::from_str(json).map_err(|err| invalid(err.to_string()))?;
+        let date = chrono::DateTime::from_timestamp(snapshot.timestamp, 0)
+            .ok_or_else(|| invalid(format!("timestamp {} out of range", snapshot.timestamp)))?
+            .date_naive();
+        let base = *find_currency(&snapshot.base).ok_or(MoneyError::UnknownCurrency(snapshot.base.clone()))?;
+
+        let mut rates = Vec::new();
+        for (code, value) in &snapshot.rates {
+            if *code == snapshot.base {
+                continue;
+            }
+            if let Some(rate) = self.currency(code)? {
+                rates.push(ImportedRate {
+                    date,
+                    rate: ExchangeRate::new(base, rate, *value)?,
+                });
+            }
+        }
+        Ok(rates)
+    }
+
+    fn rate(&self, base: Currency, code: &str, value: &str) -> Result<Option<ExchangeRate>, MoneyError> {
+        let Some(currency) = self.currency(code)? else {
+            return Ok(None);
+        };
+        let value = Decimal::from_str(value).map_err(|_| invalid(format!("invalid rate {:?} for {}", value, code)))?;
+        ExchangeRate::new(base, currency, value).map(Some)
+    }
+
+    fn currency(&self, code: &str) -> Result<Option<Currency>, MoneyError> {
+        match (find_currency(code), self.unknown) {
+            (Some(currency), _) => Ok(Some(*currency)),
+            (None, UnknownCurrencies::Skip) => Ok(None),
+            (None, UnknownCurrencies::Fail) => Err(MoneyError::UnknownCurrency(code.to_string())),
+        }
+    }
+}
+
+/// Returns the value of an XML attribute in a tag, quoted with either kind of quote.
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = tag;
+    while let Some(position) = rest.find(name) {
+        let preceded_by_space = rest[..position].ends_with(char::is_whitespace);
+        let value = rest[position + name.len()..].trim_start().strip_prefix('=').map(str::trim_start);
+        if let (true, Some(value)) = (preceded_by_space, value) {
+            let quote = value.chars().next().filter(|quote| *quote == '\'' || *quote == '"')?;
+            return value[1..].split(quote).next();
+        }
+        rest = &rest[position + name.len()..];
+    }
+    None
+}
+
+fn parse_date(date: &str) -> Result<NaiveDate, MoneyError> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(date, "%d %B %Y"))
+        .map_err(|_| invalid(format!("invalid date {:?}", date)))
+}
+
+fn invalid(reason: String) -> MoneyError {
+    MoneyError::InvalidRateFile(reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::currencies::iso::{BGN, CHF, GBP, JPY, USD};
+    use crate::{Exchange, Money};
+    use rust_decimal_macros::dec;
+
+    const DAILY_XML: &str = include_str!("../tests/fixtures/eurofxref-daily.xml");
+    const HISTORY_XML: &str = include_str!("../tests/fixtures/eurofxref-hist-90d.xml");
+    const DAILY_CSV: &str = include_str!("../tests/fixtures/eurofxref.csv");
+    const HISTORY_CSV: &str = include_str!("../tests/fixtures/eurofxref-hist.csv");
+    const OPEN_EXCHANGE_RATES: &str = include_str!("../tests/fixtures/openexchangerates-latest.json");
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn imported(date: NaiveDate, from: Currency, to: Currency, rate: Decimal) -> ImportedRate {
+        ImportedRate {
+            date,
+            rate: ExchangeRate::new(from, to, rate).unwrap(),
+        }
+    }
+
+    #[test]
+    fn import_ecb_daily() {
+        let expected = vec![
+            imported(date(2024, 6, 14), EUR, USD, dec!(1.0686)),
+            imported(date(2024, 6, 14), EUR, JPY, dec!(168.03)),
+            imported(date(2024, 6, 14), EUR, GBP, dec!(0.84405)),
+            imported(date(2024, 6, 14), EUR, CHF, dec!(0.9541)),
+        ];
+        assert_eq!(RateFormat::EcbXml.parse(DAILY_XML, UnknownCurrencies::Fail).unwrap(), expected);
+        assert_eq!(RateFormat::EcbCsv.parse(DAILY_CSV, UnknownCurrencies::Fail).unwrap(), expected);
+    }
+
+    #[test]
+    fn import_ecb_history() {
+        let xml = RateFormat::EcbXml.parse(HISTORY_XML, UnknownCurrencies::Fail).unwrap();
+        assert_eq!(xml.len(), 4);
+        assert_eq!(xml[3], imported(date(2024, 6, 13), EUR, GBP, dec!(0.84455)));
+
+        // CYP is no longer a known currency, but it has no values in these rows
+        let csv = RateFormat::EcbCsv.parse(HISTORY_CSV, UnknownCurrencies::Fail).unwrap();
+        assert_eq!(csv.len(), 8);
+        assert_eq!(csv[6], imported(date(2024, 6, 13), EUR, BGN, dec!(1.9558)));
+
+        let cyprus = HISTORY_CSV.replace("N/A", "0.585274");
+        assert_eq!(
+            RateFormat::EcbCsv.parse(&cyprus, UnknownCurrencies::Fail),
+            Err(MoneyError::UnknownCurrency("CYP".to_string()))
+        );
+        assert_eq!(RateFormat::EcbCsv.parse(&cyprus, UnknownCurrencies::Skip).unwrap(), csv);
+    }
+
+    #[test]
+    fn import_open_exchange_rates() {
+        assert_eq!(
+            RateFormat::OpenExchangeRates.parse(OPEN_EXCHANGE_RATES, UnknownCurrencies::Fail),
+            Err(MoneyError::UnknownCurrency("CNH".to_string()))
+        );
+        let rates = RateFormat::OpenExchangeRates.parse(OPEN_EXCHANGE_RATES, UnknownCurrencies::Skip).unwrap();
+        assert_eq!(
+            rates,
+            vec![
+                imported(date(2024, 6, 15), USD, EUR, dec!(0.935791)),
+                imported(date(2024, 6, 15), USD, GBP, dec!(0.789847)),
+                imported(date(2024, 6, 15), USD, JPY, dec!(157.3875)),
+            ]
+        );
+    }
+
+    #[test]
+    fn import_errors() {
+        let fail = UnknownCurrencies::Fail;
+        let undated = "<Cube><Cube currency='USD' rate='1.0686'/></Cube>";
+        assert!(matches!(RateFormat::EcbXml.parse(undated, fail), Err(MoneyError::InvalidRateFile(_))));
+        let bad_rate = "<Cube time='2024-06-14'><Cube currency='USD' rate='one'/></Cube>";
+        assert_eq!(
+            RateFormat::EcbXml.parse(bad_rate, fail),
+            Err(MoneyError::InvalidRateFile("invalid rate \"one\" for USD".to_string()))
+        );
+        let bad_date = "Date, USD\n14/06/2024, 1.0686\n";
+        assert!(matches!(RateFormat::EcbCsv.parse(bad_date, fail), Err(MoneyError::InvalidRateFile(_))));
+        assert!(matches!(RateFormat::EcbCsv.parse("", fail), Err(MoneyError::InvalidRateFile(_))));
+        let no_rates = r#"{"timestamp": 1718409600, "base": "USD"}"#;
+        assert!(matches!(RateFormat::OpenExchangeRates.parse(no_rates, fail), Err(MoneyError::InvalidRateFile(_))));
+        let unknown_base = r#"{"timestamp": 1718409600, "base": "CNH", "rates": {}}"#;
+        assert_eq!(
+            RateFormat::OpenExchangeRates.parse(unknown_base, UnknownCurrencies::Skip),
+            Err(MoneyError::UnknownCurrency("CNH".to_string()))
+        );
+    }
+
+    #[test]
+    fn import_into_exchange() {
+        let mut exchange = Exchange::new();
+        let count = exchange.import(RateFormat::EcbXml, HISTORY_XML, UnknownCurrencies::Fail).unwrap();
+        assert_eq!(count, 4);
+
+        // The latest rates become the current ones, and earlier ones stay available by date
+        assert_eq!(exchange.get_rate(&EUR, &USD), Some(ExchangeRate::new(EUR, USD, dec!(1.0686)).unwrap()));
+        let earlier = exchange.convert_at(&Money::from_major(100, EUR), &GBP, date(2024, 6, 13));
+        assert_eq!(earlier, Ok(Money::from_minor(8_446, GBP)));
+    }
+}
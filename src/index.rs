@@ -0,0 +1,256 @@
+use crate::{Amount, Money, MoneyError, NaiveDate, RoundingPolicy};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// How an `IndexSeries` finds a value for a date that falls between its observations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Interpolation {
+    /// Only dates with an observation have a value.
+    Exact,
+    /// The latest observation on or before the date is used.
+    Previous,
+    /// The value is interpolated linearly by days between the surrounding observations.
+    Linear,
+}
+
+/// A series of index values by date, such as a consumer price index, used to restate amounts between dates.
+///
+/// Dates are written as `YYYY-MM-DD`, or as `YYYY-MM` for the first day of a month. Values must be positive.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_money::{IndexSeries, Interpolation, Money, NaiveDate, RoundingPolicy};
+/// use rusty_money::currencies::iso::USD;
+/// use rust_decimal::RoundingStrategy;
+///
+/// let cpi = IndexSeries::from_csv("date,cpi\n2000-01,168.8\n2024-01,308.417\n").unwrap();
+/// let then = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap();
+/// let now = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+/// let cents = RoundingPolicy::ToCurrency(RoundingStrategy::MidpointAwayFromZero);
+///
+/// let price = Money::from_major(100, USD);
+/// let today = price.adjust_by_index(&cpi, then, now, Interpolation::Exact, cents).unwrap();
+/// assert_eq!(today, Money::from_minor(18_271, USD));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "BTreeMap<String, Decimal>", into = "BTreeMap<NaiveDate, Decimal>")]
+pub struct IndexSeries {
+    values: BTreeMap<NaiveDate, Decimal>,
+}
+
+impl IndexSeries {
+    /// Creates an empty series.
+    pub fn new() -> IndexSeries {
+        IndexSeries::default()
+    }
+
+    /// Parses a series from CSV lines of `date,value`. Blank lines are skipped, and so is the first line if
+    /// neither its date nor its value parses, as in a header.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::InvalidIndexSeries` if a line does not hold a date and a positive value.
+    pub fn from_csv(csv: &str) -> Result<IndexSeries, MoneyError> {
+        let mut series = IndexSeries::new();
+        for (number, line) in csv.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (date, value) = line.split_once(',').ok_or(MoneyError::InvalidIndexSeries)?;
+            let date = match parse_date(date.trim()) {
+                Ok(date) => date,
+                Err(_) if number == 0 && Decimal::from_str(value.trim()).is_err() => continue,
+                Err(err) => return Err(err),
+            };
+            let value = Decimal::from_str(value.trim()).map_err(|_| MoneyError::InvalidIndexSeries)?;
+            series.insert(date, value)?;
+        }
+        Ok(series)
+    }
+
+    /// Parses a series from a JSON object of dates to values, such as `{"2024-01": "308.417"}`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::InvalidIndexSeries` if the JSON is not such an object or holds an invalid date or value.
+    pub fn from_json(json: &str) -> Result<IndexSeries, MoneyError> {
+        let values: BTreeMap<String, Decimal> =
+            serde_json::from_str(json).map_err(|_| MoneyError::InvalidIndexSeries)?;
+        IndexSeries::try_from(values)
+    }
+
+    /// Adds or replaces the value for a date.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::InvalidIndexSeries` if `value` is not positive.
+    pub fn insert(&mut self, date: NaiveDate, value: Decimal) -> Result<(), MoneyError> {
+        if value <= Decimal::ZERO {
+            return Err(MoneyError::InvalidIndexSeries);
+        }
+        self.values.insert(date, value);
+        Ok(())
+    }
+
+    /// Returns the index value for a date.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::MissingIndexValue` if the series has no value for `date` with the given interpolation,
+    /// for example because it lies outside the observed range.
+    pub fn value_at(&self, date: NaiveDate, interpolation: Interpolation) -> Result<Decimal, MoneyError> {
+        if let Some(value) = self.values.get(&date) {
+            return Ok(*value);
+        }
+        let before = self.values.range(..date).next_back();
+        let after = self.values.range(date..).next();
+        match (interpolation, before, after) {
+            (Interpolation::Previous, Some((_, value)), _) => Ok(*value),
+            (Interpolation::Linear, Some((start, low)), Some((end, high))) => {
+                let elapsed = Decimal::from((date - *start).num_days());
+                let span = Decimal::from((*end - *start).num_days());
+                Ok(*low + (*high - *low) * elapsed / span)
+            }
+            _ => Err(MoneyError::MissingIndexValue),
+        }
+    }
+
+    /// Returns the factor that restates an amount from the index level of `from` to that of `to`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::MissingIndexValue` if either date has no value.
+    pub fn factor(&self, from: NaiveDate, to: NaiveDate, interpolation: Interpolation) -> Result<Decimal, MoneyError> {
+        let from = self.value_at(from, interpolation)?;
+        let to = self.value_at(to, interpolation)?;
+        Ok(to / from)
+    }
+
+    /// Returns an iterator over the observations in date order.
+    pub fn iter(&self) -> impl Iterator<Item = (&NaiveDate, &Decimal)> {
+        self.values.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl TryFrom<BTreeMap<String, Decimal>> for IndexSeries {
+    type Error = MoneyError;
+
+    fn try_from(values: BTreeMap<String, Decimal>) -> Result<Self, Self::Error> {
+        let mut series = IndexSeries::new();
+        for (date, value) in values {
+            series.insert(parse_date(&date)?, value)?;
+        }
+        Ok(series)
+    }
+}
+
+impl From<IndexSeries> for BTreeMap<NaiveDate, Decimal> {
+    fn from(series: IndexSeries) -> Self {
+        series.values
+    }
+}
+
+fn parse_date(date: &str) -> Result<NaiveDate, MoneyError> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(&format!("{}-01", date), "%Y-%m-%d"))
+        .map_err(|_| MoneyError::InvalidIndexSeries)
+}
+
+impl<A: Amount> Money<A> {
+    /// Restates this amount from the purchasing power at `from` to the purchasing power at `to`.
+    ///
+    /// The amount is multiplied by the ratio of the index values at the two dates and then rounded.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::MissingIndexValue` if the series has no value for either date.
+    pub fn adjust_by_index(
+        &self,
+        series: &IndexSeries,
+        from: NaiveDate,
+        to: NaiveDate,
+        interpolation: Interpolation,
+        rounding: RoundingPolicy,
+    ) -> Result<Money<A>, MoneyError> {
+        let factor = series.factor(from, to, interpolation)?;
+        Ok(Money::from_amount(self.amount().mul_decimal(factor), *self.currency()).round_with(rounding))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::currencies::iso::USD;
+    use crate::FixedPoint;
+    use rust_decimal_macros::dec;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn cpi() -> IndexSeries {
+        IndexSeries::from_csv("2024-01-01,300\n\n2024-01-31, 303\n2024-03-01,310\n").unwrap()
+    }
+
+    #[test]
+    fn index_interpolation() {
+        let cpi = cpi();
+        assert_eq!(cpi.len(), 3);
+        assert_eq!(cpi.value_at(date(2024, 1, 31), Interpolation::Exact), Ok(dec!(303)));
+        assert_eq!(cpi.value_at(date(2024, 1, 11), Interpolation::Exact), Err(MoneyError::MissingIndexValue));
+        assert_eq!(cpi.value_at(date(2024, 1, 11), Interpolation::Previous), Ok(dec!(300)));
+        assert_eq!(cpi.value_at(date(2024, 1, 11), Interpolation::Linear), Ok(dec!(301)));
+        assert_eq!(cpi.value_at(date(2024, 3, 2), Interpolation::Previous), Ok(dec!(310)));
+        assert_eq!(cpi.value_at(date(2024, 3, 2), Interpolation::Linear), Err(MoneyError::MissingIndexValue));
+        assert_eq!(cpi.value_at(date(2023, 12, 31), Interpolation::Previous), Err(MoneyError::MissingIndexValue));
+    }
+
+    #[test]
+    fn index_adjust_money() {
+        let cpi = cpi();
+        let price = Money::from_major(1_000, USD);
+        let unrounded = RoundingPolicy::Unrounded;
+        let adjusted = price.adjust_by_index(&cpi, date(2024, 1, 1), date(2024, 3, 1), Interpolation::Exact, unrounded);
+        assert_eq!(adjusted.unwrap(), Money::from_major(1_000, USD) * dec!(310) / dec!(300));
+
+        // Deflating back to the base date restores the amount
+        let rounding = RoundingPolicy::ToCurrency(rust_decimal::RoundingStrategy::MidpointNearestEven);
+        let later = Money::from_minor(103_333, USD);
+        let earlier = later.adjust_by_index(&cpi, date(2024, 3, 1), date(2024, 1, 1), Interpolation::Exact, rounding);
+        assert_eq!(earlier.unwrap(), Money::from_minor(100_000, USD));
+
+        let fixed: Money<FixedPoint<2>> = Money::parse("1000.00 USD").unwrap();
+        let linear = Interpolation::Linear;
+        let adjusted = fixed.adjust_by_index(&cpi, date(2024, 1, 1), date(2024, 1, 11), linear, rounding);
+        assert_eq!(adjusted, Ok(Money::parse("1003.33 USD").unwrap()));
+    }
+
+    #[test]
+    fn index_parsing() {
+        let json = IndexSeries::from_json(r#"{"2024-01": "300", "2024-01-31": 303, "2024-03-01": "310"}"#).unwrap();
+        assert_eq!(json, cpi());
+        assert_eq!(serde_json::from_str::<IndexSeries>(&serde_json::to_string(&json).unwrap()).unwrap(), json);
+
+        assert_eq!(IndexSeries::from_csv("2024-01-01;300"), Err(MoneyError::InvalidIndexSeries));
+        assert_eq!(IndexSeries::from_csv("date,value\nJan 2024,300"), Err(MoneyError::InvalidIndexSeries));
+        assert_eq!(IndexSeries::from_csv("2024-01-01,abc"), Err(MoneyError::InvalidIndexSeries));
+        // A first line with a value is data, so a bad date in it is an error rather than a header
+        assert_eq!(IndexSeries::from_csv("2024-13-01,300\n2024-02-01,301"), Err(MoneyError::InvalidIndexSeries));
+        assert_eq!(IndexSeries::from_csv("2024-01-01,0"), Err(MoneyError::InvalidIndexSeries));
+        assert_eq!(IndexSeries::from_json(r#"{"2024-13": "300"}"#), Err(MoneyError::InvalidIndexSeries));
+        assert_eq!(IndexSeries::from_json(r#"[1, 2]"#), Err(MoneyError::InvalidIndexSeries));
+        assert!(serde_json::from_str::<IndexSeries>(r#"{"2024-01": "-1"}"#).is_err());
+    }
+}
@@ -0,0 +1,301 @@
+pub use chrono::NaiveDate;
+
+use crate::{Amount, Money, MoneyError, RoundingPolicy};
+use chrono::Datelike;
+use rust_decimal::{Decimal, MathematicalOps};
+
+/// Day-count conventions, which decide how much of a year lies between two dates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DayCount {
+    /// Actual days elapsed over a 360 day year, common for money market instruments.
+    Act360,
+    /// Actual days elapsed over a fixed 365 day year, ignoring leap years.
+    Act365Fixed,
+    /// Every month counts as 30 days in a 360 day year (the ISDA bond basis rules for the 31st).
+    Thirty360,
+    /// Actual days elapsed, with days in leap years divided by 366 and the others by 365 (ACT/ACT ISDA).
+    ActAct,
+}
+
+impl DayCount {
+    /// Returns the number of days between two dates under this convention.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::InvalidDateRange` if `end` is before `start`.
+    pub fn days(&self, start: NaiveDate, end: NaiveDate) -> Result<i64, MoneyError> {
+        if end < start {
+            return Err(MoneyError::InvalidDateRange);
+        }
+        match self {
+            DayCount::Thirty360 => {
+                let d1 = start.day().min(30);
+                // The 31st only counts as the 30th when the period started at the end of a month
+                let d2 = if d1 == 30 { end.day().min(30) } else { end.day() };
+                Ok(360 * i64::from(end.year() - start.year())
+                    + 30 * (i64::from(end.month()) - i64::from(start.month()))
+                    + (i64::from(d2) - i64::from(d1)))
+            }
+            _ => Ok((end - start).num_days()),
+        }
+    }
+
+    /// Returns the fraction of a year between two dates under this convention.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::{DayCount, NaiveDate};
+    /// use rust_decimal_macros::dec;
+    ///
+    /// let start = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2024, 3, 31).unwrap();
+    /// assert_eq!(DayCount::Thirty360.year_fraction(start, end).unwrap(), dec!(60) / dec!(360));
+    /// assert_eq!(DayCount::Act360.year_fraction(start, end).unwrap(), dec!(60) / dec!(360));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::InvalidDateRange` if `end` is before `start`.
+    pub fn year_fraction(&self, start: NaiveDate, end: NaiveDate) -> Result<Decimal, MoneyError> {
+        let days = Decimal::from(self.days(start, end)?);
+        match self {
+            DayCount::Act360 | DayCount::Thirty360 => Ok(days / Decimal::from(360)),
+            DayCount::Act365Fixed => Ok(days / Decimal::from(365)),
+            DayCount::ActAct => {
+                // Split the period at each new year and weigh every piece by the length of its own year
+                let mut fraction = Decimal::ZERO;
+                let mut from = start;
+                while from < end {
+                    let next_year = NaiveDate::from_ymd_opt(from.year() + 1, 1, 1).ok_or(MoneyError::Overflow)?;
+                    let to = next_year.min(end);
+                    let year_days = if from.leap_year() { 366 } else { 365 };
+                    fraction += Decimal::from((to - from).num_days()) / Decimal::from(year_days);
+                    from = to;
+                }
+                Ok(fraction)
+            }
+        }
+    }
+}
+
+/// How often interest is compounded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Compounding {
+    /// Interest is never added to the principal.
+    Simple,
+    /// Once per year.
+    Annual,
+    /// Twelve times per year.
+    Monthly,
+    /// 365 times per year.
+    Daily,
+    /// Continuously, growing by `e^(rate * years)`.
+    Continuous,
+}
+
+impl Compounding {
+    /// Returns the growth factor of one unit of principal at an annual `rate` over `years`.
+    pub(crate) fn growth_factor(&self, rate: Decimal, years: Decimal) -> Result<Decimal, MoneyError> {
+        let periodic = |periods_per_year: i64| {
+            let base = Decimal::ONE + rate / Decimal::from(periods_per_year);
+            let periods = years * Decimal::from(periods_per_year);
+            // Whole periods are raised exactly, only the stub period needs the (approximate) real power
+            let whole = periods.trunc();
+            let stub = periods - whole;
+            let mut factor = base.checked_powi(i64::try_from(whole).ok()?)?;
+            if !stub.is_zero() {
+                factor = factor.checked_mul(base.checked_powd(stub)?)?;
+            }
+            Some(factor)
+        };
+        let factor = match self {
+            Compounding::Simple => Some(Decimal::ONE + rate * years),
+            Compounding::Annual => periodic(1),
+            Compounding::Monthly => periodic(12),
+            Compounding::Daily => periodic(365),
+            Compounding::Continuous => (rate * years).checked_exp(),
+        };
+        factor.ok_or(MoneyError::Overflow)
+    }
+}
+
+impl<A: Amount> Money<A> {
+    /// Returns the simple interest earned on this principal at an annual `rate` between two dates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::{DayCount, Money, NaiveDate, RoundingPolicy};
+    /// use rusty_money::currencies::iso::USD;
+    /// use rust_decimal::RoundingStrategy;
+    /// use rust_decimal_macros::dec;
+    ///
+    /// let deposit = Money::from_major(10_000, USD);
+    /// let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2024, 7, 1).unwrap();
+    /// let policy = RoundingPolicy::ToCurrency(RoundingStrategy::MidpointAwayFromZero);
+    ///
+    /// // 182 days at 5% on an ACT/360 basis
+    /// let interest = deposit.simple_interest(dec!(0.05), start, end, DayCount::Act360, policy).unwrap();
+    /// assert_eq!(interest, Money::from_minor(25_278, USD));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::InvalidDateRange` if `end` is before `start`.
+    pub fn simple_interest(
+        &self,
+        rate: Decimal,
+        start: NaiveDate,
+        end: NaiveDate,
+        day_count: DayCount,
+        rounding: RoundingPolicy,
+    ) -> Result<Money<A>, MoneyError> {
+        self.compound_interest(rate, start, end, day_count, Compounding::Simple, rounding)
+    }
+
+    /// Returns the interest earned on this principal at an annual `rate` between two dates, compounded as given.
+    ///
+    /// The returned amount is the interest only; add it to the principal for the final balance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::{Compounding, DayCount, Money, NaiveDate, RoundingPolicy};
+    /// use rusty_money::currencies::iso::USD;
+    /// use rust_decimal::RoundingStrategy;
+    /// use rust_decimal_macros::dec;
+    ///
+    /// let deposit = Money::from_major(1_000, USD);
+    /// let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+    /// let policy = RoundingPolicy::ToCurrency(RoundingStrategy::MidpointAwayFromZero);
+    ///
+    /// let interest = deposit
+    ///     .compound_interest(dec!(0.05), start, end, DayCount::Thirty360, Compounding::Monthly, policy)
+    ///     .unwrap();
+    /// assert_eq!(interest, Money::from_minor(5_116, USD));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::InvalidDateRange` if `end` is before `start` and `MoneyError::Overflow` if the growth
+    /// factor cannot be represented.
+    pub fn compound_interest(
+        &self,
+        rate: Decimal,
+        start: NaiveDate,
+        end: NaiveDate,
+        day_count: DayCount,
+        compounding: Compounding,
+        rounding: RoundingPolicy,
+    ) -> Result<Money<A>, MoneyError> {
+        let years = day_count.year_fraction(start, end)?;
+        let growth = compounding.growth_factor(rate, years)? - Decimal::ONE;
+        let interest = self.amount().mul_decimal(growth);
+        Ok(Money::from_amount(interest, *self.currency()).round_with(rounding))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::currencies::iso::USD;
+    use crate::FixedPoint;
+    use rust_decimal::RoundingStrategy;
+    use rust_decimal_macros::dec;
+
+    const CENTS: RoundingPolicy = RoundingPolicy::ToCurrency(RoundingStrategy::MidpointAwayFromZero);
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn day_count_conventions() {
+        let (start, end) = (date(2023, 7, 1), date(2024, 7, 1));
+        assert_eq!(DayCount::Act360.days(start, end), Ok(366));
+        assert_eq!(DayCount::Act360.year_fraction(start, end), Ok(dec!(366) / dec!(360)));
+        assert_eq!(DayCount::Act365Fixed.year_fraction(start, end), Ok(dec!(366) / dec!(365)));
+        assert_eq!(DayCount::Thirty360.year_fraction(start, end), Ok(dec!(1)));
+        assert_eq!(
+            DayCount::ActAct.year_fraction(start, end),
+            Ok(dec!(184) / dec!(365) + dec!(182) / dec!(366))
+        );
+        assert_eq!(DayCount::ActAct.year_fraction(date(2024, 1, 1), date(2025, 1, 1)), Ok(dec!(1)));
+
+        // 30/360 end of month rules
+        assert_eq!(DayCount::Thirty360.days(date(2024, 1, 31), date(2024, 3, 31)), Ok(60));
+        assert_eq!(DayCount::Thirty360.days(date(2024, 1, 15), date(2024, 3, 31)), Ok(76));
+        assert_eq!(DayCount::Thirty360.days(date(2024, 2, 29), date(2024, 3, 31)), Ok(32));
+
+        assert_eq!(DayCount::Act360.days(end, start), Err(MoneyError::InvalidDateRange));
+    }
+
+    #[test]
+    fn simple_interest() {
+        let principal = Money::from_major(10_000, USD);
+        let (start, end) = (date(2024, 1, 1), date(2024, 7, 1));
+        let interest = |day_count| principal.simple_interest(dec!(0.05), start, end, day_count, CENTS);
+        assert_eq!(interest(DayCount::Act360), Ok(Money::from_minor(25_278, USD)));
+        assert_eq!(interest(DayCount::Act365Fixed), Ok(Money::from_minor(24_932, USD)));
+        assert_eq!(interest(DayCount::Thirty360), Ok(Money::from_minor(25_000, USD)));
+        assert_eq!(
+            principal.simple_interest(dec!(0.05), start, start, DayCount::Act360, CENTS),
+            Ok(Money::from_minor(0, USD))
+        );
+    }
+
+    #[test]
+    fn compound_interest() {
+        let principal = Money::from_major(1_000, USD);
+        let (start, end) = (date(2024, 1, 1), date(2025, 1, 1));
+        let interest = |compounding| {
+            principal.compound_interest(dec!(0.05), start, end, DayCount::Thirty360, compounding, CENTS)
+        };
+        assert_eq!(interest(Compounding::Simple), Ok(Money::from_minor(5_000, USD)));
+        assert_eq!(interest(Compounding::Annual), Ok(Money::from_minor(5_000, USD)));
+        assert_eq!(interest(Compounding::Monthly), Ok(Money::from_minor(5_116, USD)));
+        assert_eq!(interest(Compounding::Daily), Ok(Money::from_minor(5_127, USD)));
+        assert_eq!(interest(Compounding::Continuous), Ok(Money::from_minor(5_127, USD)));
+
+        // A half year of annual compounding uses the fractional power: 1000 * (1.05^0.5 - 1)
+        let half = principal.compound_interest(
+            dec!(0.05),
+            start,
+            date(2024, 7, 1),
+            DayCount::Thirty360,
+            Compounding::Annual,
+            CENTS,
+        );
+        assert_eq!(half, Ok(Money::from_minor(2_470, USD)));
+
+        // Ten years of monthly compounding: 1000 * ((1 + 0.05/12)^120 - 1)
+        let decade = principal.compound_interest(
+            dec!(0.05),
+            start,
+            date(2034, 1, 1),
+            DayCount::Thirty360,
+            Compounding::Monthly,
+            RoundingPolicy::Unrounded,
+        );
+        assert_eq!(decade.unwrap().round(2, RoundingStrategy::MidpointAwayFromZero), Money::from_minor(64_701, USD));
+    }
+
+    #[test]
+    fn interest_on_other_backends() {
+        let principal = Money::<FixedPoint<4>>::parse("1000 USD").unwrap();
+        let interest = principal
+            .compound_interest(
+                dec!(0.05),
+                date(2024, 1, 1),
+                date(2025, 1, 1),
+                DayCount::Thirty360,
+                Compounding::Monthly,
+                CENTS,
+            )
+            .unwrap();
+        assert_eq!(interest, Money::parse("51.16 USD").unwrap());
+    }
+}
@@ -0,0 +1,404 @@
+use crate::{Amount, Currency, Money, MoneyError, TaxLine, TaxScheme};
+use rust_decimal::{Decimal, RoundingStrategy};
+
+/// A discount on a line or on a whole invoice.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Discount<A = Decimal> {
+    /// A fraction of the amount, e.g. `0.15` for 15% off.
+    Percentage(Decimal),
+    /// A fixed amount off.
+    Fixed(Money<A>),
+}
+
+impl<A: Amount> Discount<A> {
+    /// Returns the unrounded discount on `amount`.
+    fn on(&self, amount: &Money<A>) -> Result<Money<A>, MoneyError> {
+        match self {
+            Discount::Percentage(rate) => {
+                Ok(Money::from_amount(amount.amount().mul_decimal(*rate), *amount.currency()))
+            }
+            Discount::Fixed(money) if money.currency() != amount.currency() => Err(MoneyError::CurrencyMismatch),
+            Discount::Fixed(money) => Ok(money.clone()),
+        }
+    }
+}
+
+/// A line of an invoice: a quantity of something at a unit price, with an optional discount.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineItem<A = Decimal> {
+    pub description: String,
+    pub quantity: Decimal,
+    pub unit_price: Money<A>,
+    pub discount: Option<Discount<A>>,
+}
+
+impl<A: Amount> LineItem<A> {
+    pub fn new(description: &str, quantity: Decimal, unit_price: Money<A>) -> LineItem<A> {
+        LineItem {
+            description: description.to_string(),
+            quantity,
+            unit_price,
+            discount: None,
+        }
+    }
+
+    /// Returns the line with a discount applied to its extended price.
+    pub fn with_discount(mut self, discount: Discount<A>) -> LineItem<A> {
+        self.discount = Some(discount);
+        self
+    }
+
+    /// Quantity times unit price, less the line discount, at full precision.
+    fn exact_amount(&self) -> Result<Money<A>, MoneyError> {
+        let extended = self.unit_price.amount().mul_decimal(self.quantity);
+        let extended = Money::from_amount(extended, *self.unit_price.currency());
+        match &self.discount {
+            Some(discount) => extended.clone() - discount.on(&extended)?,
+            None => Ok(extended),
+        }
+    }
+}
+
+/// An invoice or shopping basket made of line items, with an optional invoice discount, taxes and shipping.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_money::{Discount, Invoice, LineItem, Money, TaxRate, TaxRounding, TaxScheme};
+/// use rusty_money::currencies::iso::EUR;
+/// use rust_decimal::RoundingStrategy;
+/// use rust_decimal_macros::dec;
+///
+/// let mut invoice = Invoice::new(EUR, RoundingStrategy::MidpointAwayFromZero);
+/// invoice.add_line(LineItem::new("Widget", dec!(3), Money::from_minor(333, EUR))).unwrap();
+/// invoice.add_line(LineItem::new("Gadget", dec!(1), Money::from_minor(2_000, EUR))).unwrap();
+/// invoice.discount = Some(Discount::Percentage(dec!(0.10)));
+/// invoice.shipping = Some(Money::from_minor(495, EUR));
+/// invoice.tax = Some(TaxScheme::new(
+///     vec![TaxRate::new("VAT", dec!(0.20))],
+///     TaxRounding::PerInvoice,
+///     RoundingStrategy::MidpointAwayFromZero,
+/// ));
+///
+/// let totals = invoice.totals().unwrap();
+/// assert_eq!(totals.subtotal, Money::from_minor(2_999, EUR));
+/// assert_eq!(totals.discount, Money::from_minor(300, EUR));
+/// assert_eq!(totals.tax, Money::from_minor(540, EUR));
+/// assert_eq!(totals.total, Money::from_minor(3_734, EUR));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Invoice<A = Decimal> {
+    currency: Currency,
+    lines: Vec<LineItem<A>>,
+    /// Discount on the subtotal, spread over the lines in proportion to their amounts.
+    pub discount: Option<Discount<A>>,
+    /// Taxes charged on the discounted lines.
+    pub tax: Option<TaxScheme>,
+    /// Shipping charged after taxes. Add taxable shipping as a line item instead.
+    pub shipping: Option<Money<A>>,
+    /// Rounding applied to line amounts and the invoice discount.
+    pub strategy: RoundingStrategy,
+}
+
+/// A line of computed invoice totals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvoiceLine<A = Decimal> {
+    pub description: String,
+    /// Quantity times unit price less the line discount, rounded and balanced against the subtotal.
+    pub amount: Money<A>,
+    /// This line's share of the invoice discount.
+    pub discount: Money<A>,
+    /// This line's share of the taxes.
+    pub tax: Money<A>,
+    /// Amount less discount plus tax.
+    pub total: Money<A>,
+}
+
+/// The computed totals of an `Invoice`.
+///
+/// Every column of `lines` adds up exactly to the matching total, and
+/// `subtotal - discount + tax + shipping == total`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvoiceTotals<A = Decimal> {
+    pub lines: Vec<InvoiceLine<A>>,
+    pub subtotal: Money<A>,
+    pub discount: Money<A>,
+    /// Taxes per rate, empty when the invoice has no tax scheme.
+    pub taxes: Vec<TaxLine<A>>,
+    pub tax: Money<A>,
+    pub shipping: Money<A>,
+    pub total: Money<A>,
+}
+
+impl<A: Amount> Invoice<A> {
+    /// Creates an empty invoice in a currency, rounding its amounts with `strategy`.
+    pub fn new(currency: Currency, strategy: RoundingStrategy) -> Invoice<A> {
+        Invoice {
+            currency,
+            lines: Vec::new(),
+            discount: None,
+            tax: None,
+            shipping: None,
+            strategy,
+        }
+    }
+
+    /// Adds a line item.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::CurrencyMismatch` if the line is priced in a different currency than the invoice.
+    pub fn add_line(&mut self, line: LineItem<A>) -> Result<(), MoneyError> {
+        if *line.unit_price.currency() != self.currency {
+            return Err(MoneyError::CurrencyMismatch);
+        }
+        self.lines.push(line);
+        Ok(())
+    }
+
+    /// Returns the line items.
+    pub fn lines(&self) -> &[LineItem<A>] {
+        &self.lines
+    }
+
+    /// Computes the line amounts, discounts, taxes and totals of the invoice.
+    ///
+    /// Line amounts are rounded so they add up to the rounded subtotal: when rounding each line on its own would
+    /// miss it, the lines that lost the most to rounding are adjusted by one minor unit at a time. The invoice
+    /// discount is spread over the lines with `Money::allocate_weighted`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::EmptySum` without lines, `MoneyError::CurrencyMismatch` if a discount or the shipping is
+    /// in another currency, `MoneyError::InvalidRatio` if there is an invoice discount or a tax scheme with
+    /// per-invoice rounding while a line amount is negative, and `MoneyError::Overflow` if a column does not fit
+    /// into the amount type.
+    pub fn totals(&self) -> Result<InvoiceTotals<A>, MoneyError> {
+        if self.lines.is_empty() {
+            return Err(MoneyError::EmptySum);
+        }
+        let currency = self.currency;
+        let zero = || Money::from_amount(A::zero(), currency);
+        let exponent = currency.exponent;
+
+        let exact: Vec<Money<A>> = self.lines.iter().map(|line| line.exact_amount()).collect::<Result<_, _>>()?;
+        let amounts = balance_rounding(&exact, exponent, self.strategy)?;
+        let subtotal = sum(&amounts, currency)?;
+
+        let discount = match &self.discount {
+            Some(discount) => discount.on(&subtotal)?.round(exponent, self.strategy),
+            None => zero(),
+        };
+        let discounts = if discount.is_zero() {
+            vec![zero(); amounts.len()]
+        } else {
+            let weights: Vec<A> = amounts.iter().map(|amount| amount.amount().clone()).collect();
+            discount.allocate_weighted(&weights)?
+        };
+        let nets: Vec<Money<A>> = amounts
+            .iter()
+            .zip(&discounts)
+            .map(|(amount, discount)| amount.clone() - discount.clone())
+            .collect::<Result<_, _>>()?;
+
+        let (line_taxes, taxes) = match &self.tax {
+            Some(scheme) => {
+                let taxed = scheme.add_tax_to_lines(&nets)?;
+                let line_taxes = taxed.lines.iter().map(|line| line.total_tax()).collect();
+                (line_taxes, taxed.total.taxes)
+            }
+            None => (vec![zero(); nets.len()], Vec::new()),
+        };
+        let tax = sum(&line_taxes, currency)?;
+
+        let shipping = self.shipping.clone().unwrap_or_else(zero);
+        if *shipping.currency() != currency {
+            return Err(MoneyError::CurrencyMismatch);
+        }
+
+        let mut lines = Vec::with_capacity(self.lines.len());
+        for (index, item) in self.lines.iter().enumerate() {
+            lines.push(InvoiceLine {
+                description: item.description.clone(),
+                amount: amounts[index].clone(),
+                discount: discounts[index].clone(),
+                tax: line_taxes[index].clone(),
+                total: (nets[index].clone() + line_taxes[index].clone())?,
+            });
+        }
+        let total = ((subtotal.clone() - discount.clone())? + tax.clone())?;
+        let total = (total + shipping.clone())?;
+
+        Ok(InvoiceTotals {
+            lines,
+            subtotal,
+            discount,
+            taxes,
+            tax,
+            shipping,
+            total,
+        })
+    }
+}
+
+fn sum<A: Amount>(monies: &[Money<A>], currency: Currency) -> Result<Money<A>, MoneyError> {
+    let total = monies.iter().try_fold(A::zero(), |acc, money| acc.checked_add(money.amount()));
+    Ok(Money::from_amount(total.ok_or(MoneyError::Overflow)?, currency))
+}
+
+/// Rounds every amount so that the rounded amounts add up to their rounded exact sum.
+///
+/// Rounding each amount on its own can miss the rounded sum by a few minor units. Those units are handed out to
+/// (or taken from) the amounts with the largest rounding error, so no amount moves further than needed.
+fn balance_rounding<A: Amount>(
+    exact: &[Money<A>],
+    exponent: u32,
+    strategy: RoundingStrategy,
+) -> Result<Vec<Money<A>>, MoneyError> {
+    let currency = *exact[0].currency();
+    let target = sum(exact, currency)?.round(exponent, strategy);
+    let mut rounded: Vec<Money<A>> = exact.iter().map(|money| money.round(exponent, strategy)).collect();
+    let unit = A::one() / A::pow10(exponent);
+
+    loop {
+        let difference = target.amount().clone() - sum(&rounded, currency)?.amount().clone();
+        if difference.is_zero() {
+            return Ok(rounded);
+        }
+        // A positive difference goes to the amount that was rounded down the most, a negative one is taken from
+        // the amount that was rounded up the most.
+        let errors = exact.iter().zip(&rounded).map(|(e, r)| e.amount().clone() - r.amount().clone());
+        let index = if difference.is_negative() {
+            errors.enumerate().min_by(|a, b| a.1.cmp(&b.1)).map(|(i, _)| i)
+        } else {
+            errors.enumerate().max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0))).map(|(i, _)| i)
+        }
+        .expect("exact amounts are not empty");
+        let step = if difference.is_negative() { -unit.clone() } else { unit.clone() };
+        rounded[index] = Money::from_amount(rounded[index].amount().clone() + step, currency);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::currencies::iso::{EUR, USD};
+    use crate::{FixedPoint, TaxRate, TaxRounding};
+    use rust_decimal_macros::dec;
+
+    fn usd(minor: i64) -> Money {
+        Money::from_minor(minor, USD)
+    }
+
+    fn invoice(lines: Vec<LineItem>) -> Invoice {
+        let mut invoice = Invoice::new(USD, RoundingStrategy::MidpointAwayFromZero);
+        for line in lines {
+            invoice.add_line(line).unwrap();
+        }
+        invoice
+    }
+
+    fn assert_reconciles(totals: &InvoiceTotals) {
+        let column = |f: fn(&InvoiceLine) -> &Money| {
+            let values: Vec<Money> = totals.lines.iter().map(|l| f(l).clone()).collect();
+            sum(&values, USD).unwrap()
+        };
+        assert_eq!(column(|l| &l.amount), totals.subtotal);
+        assert_eq!(column(|l| &l.discount), totals.discount);
+        assert_eq!(column(|l| &l.tax), totals.tax);
+        assert_eq!((column(|l| &l.total) + totals.shipping.clone()).unwrap(), totals.total);
+    }
+
+    #[test]
+    fn invoice_line_amounts() {
+        let totals = invoice(vec![
+            LineItem::new("Apples", dec!(2.5), usd(199)),
+            LineItem::new("Pears", dec!(4), usd(125)).with_discount(Discount::Percentage(dec!(0.25))),
+            LineItem::new("Plums", dec!(1), usd(1_000)).with_discount(Discount::Fixed(usd(150))),
+        ])
+        .totals()
+        .unwrap();
+        let amounts: Vec<Money> = totals.lines.iter().map(|l| l.amount.clone()).collect();
+        assert_eq!(amounts, vec![usd(498), usd(375), usd(850)]);
+        assert_eq!(totals.subtotal, usd(1_723));
+        assert_eq!(totals.total, usd(1_723));
+        assert_reconciles(&totals);
+    }
+
+    #[test]
+    fn invoice_penny_balancing() {
+        // Three lines of 1/3 each round to 0.33 but add up to 1.00
+        let third = Money::from_decimal(dec!(1) / dec!(3), USD);
+        let totals = invoice(vec![LineItem::new("Share", dec!(1), third); 3]).totals().unwrap();
+        assert_eq!(totals.subtotal, usd(100));
+        let amounts: Vec<Money> = totals.lines.iter().map(|l| l.amount.clone()).collect();
+        assert_eq!(amounts, vec![usd(34), usd(33), usd(33)]);
+
+        // And the other way around: three halves of a cent round up
+        let totals = invoice(vec![LineItem::new("Part", dec!(1), Money::from_decimal(dec!(0.005), USD)); 3])
+            .totals()
+            .unwrap();
+        assert_eq!(totals.subtotal, usd(2));
+        assert_reconciles(&totals);
+    }
+
+    #[test]
+    fn invoice_discount_and_tax() {
+        let mut invoice = invoice(vec![
+            LineItem::new("A", dec!(1), usd(1_000)),
+            LineItem::new("B", dec!(1), usd(2_000)),
+            LineItem::new("C", dec!(1), usd(333)),
+        ]);
+        invoice.discount = Some(Discount::Fixed(usd(100)));
+        invoice.tax = Some(TaxScheme::new(
+            vec![TaxRate::new("Sales tax", dec!(0.0825))],
+            TaxRounding::PerLine,
+            RoundingStrategy::MidpointAwayFromZero,
+        ));
+        invoice.shipping = Some(usd(599));
+
+        let totals = invoice.totals().unwrap();
+        let discounts: Vec<Money> = totals.lines.iter().map(|l| l.discount.clone()).collect();
+        assert_eq!(discounts, vec![usd(31), usd(60), usd(9)]);
+        assert_eq!(totals.taxes.len(), 1);
+        assert_eq!(totals.tax, usd(267));
+        assert_eq!(totals.total, usd(3_333 - 100 + 267 + 599));
+        assert_reconciles(&totals);
+    }
+
+    #[test]
+    fn invoice_errors() {
+        let empty: Invoice = Invoice::new(USD, RoundingStrategy::MidpointAwayFromZero);
+        assert_eq!(empty.totals(), Err(MoneyError::EmptySum));
+
+        let mut invoice = invoice(vec![LineItem::new("A", dec!(1), usd(1_000))]);
+        assert_eq!(
+            invoice.add_line(LineItem::new("B", dec!(1), Money::from_minor(1, EUR))),
+            Err(MoneyError::CurrencyMismatch)
+        );
+        invoice.shipping = Some(Money::from_minor(100, EUR));
+        assert_eq!(invoice.totals(), Err(MoneyError::CurrencyMismatch));
+        invoice.shipping = None;
+        invoice.discount = Some(Discount::Fixed(Money::from_minor(100, EUR)));
+        assert_eq!(invoice.totals(), Err(MoneyError::CurrencyMismatch));
+
+        let huge = Money::from_decimal(Decimal::MAX, USD);
+        invoice.discount = None;
+        invoice.add_line(LineItem::new("B", dec!(1), huge.clone())).unwrap();
+        invoice.add_line(LineItem::new("C", dec!(1), huge)).unwrap();
+        assert_eq!(invoice.totals(), Err(MoneyError::Overflow));
+    }
+
+    #[test]
+    fn invoice_fixed_point() {
+        let mut invoice: Invoice<FixedPoint<4>> = Invoice::new(EUR, RoundingStrategy::MidpointNearestEven);
+        invoice
+            .add_line(LineItem::new("Fuel", dec!(41.37), Money::parse("1.799 EUR").unwrap()))
+            .unwrap();
+        invoice.discount = Some(Discount::Percentage(dec!(0.03)));
+        let totals = invoice.totals().unwrap();
+        assert_eq!(totals.subtotal, Money::parse("74.42 EUR").unwrap());
+        assert_eq!(totals.discount, Money::parse("2.23 EUR").unwrap());
+        assert_eq!(totals.total, Money::parse("72.19 EUR").unwrap());
+    }
+}
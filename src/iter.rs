@@ -0,0 +1,158 @@
+use crate::{stats, Amount, Currency, Exchange, Money, MoneyBag, MoneyError, RoundingPolicy};
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
+
+/// Extension methods for iterators over `Money` or `&Money`.
+///
+/// Unlike `impl Sum for Money`, these never panic: empty input and mixed currencies are reported as errors.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_money::{Money, MoneyIterator};
+/// use rusty_money::currencies::iso::{EUR, USD};
+///
+/// let payments = vec![Money::from_minor(1_000, USD), Money::from_minor(250, USD), Money::from_minor(300, EUR)];
+///
+/// let usd_total = payments.iter().filter(|m| m.currency() == &USD).try_sum().unwrap();
+/// assert_eq!(usd_total, Money::from_minor(1_250, USD));
+///
+/// let by_currency = payments.iter().sum_by_currency();
+/// assert_eq!(by_currency["EUR"], Money::from_minor(300, EUR));
+/// ```
+pub trait MoneyIterator<A: Amount>: Iterator + Sized
+where
+    Self::Item: Borrow<Money<A>>,
+{
+    /// Sums the items.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::EmptySum` for an empty iterator and `MoneyError::CurrencyMismatch` for mixed currencies.
+    fn try_sum(self) -> Result<Money<A>, MoneyError> {
+        let mut total: Option<Money<A>> = None;
+        for item in self {
+            let item = item.borrow();
+            total = Some(match total {
+                None => item.clone(),
+                Some(sum) => (sum + item.clone())?,
+            });
+        }
+        total.ok_or(MoneyError::EmptySum)
+    }
+
+    /// Returns the smallest item.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::EmptySum` for an empty iterator and `MoneyError::CurrencyMismatch` for mixed currencies.
+    fn try_min(self) -> Result<Money<A>, MoneyError> {
+        stats::min(self)
+    }
+
+    /// Returns the largest item.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::EmptySum` for an empty iterator and `MoneyError::CurrencyMismatch` for mixed currencies.
+    fn try_max(self) -> Result<Money<A>, MoneyError> {
+        stats::max(self)
+    }
+
+    /// Sums the items separately for each currency, keyed by currency code.
+    ///
+    /// Currencies whose items cancel out are kept with a zero total.
+    fn sum_by_currency(self) -> BTreeMap<&'static str, Money<A>> {
+        let mut totals: BTreeMap<&'static str, Money<A>> = BTreeMap::new();
+        for item in self {
+            let item = item.borrow();
+            match totals.remove(item.currency().code) {
+                Some(sum) => {
+                    let sum = Money::from_amount(sum.amount().clone() + item.amount().clone(), *item.currency());
+                    totals.insert(item.currency().code, sum);
+                }
+                None => {
+                    totals.insert(item.currency().code, item.clone());
+                }
+            }
+        }
+        totals
+    }
+
+    /// Sums the items into a single currency, converting items in other currencies with the rates in an `Exchange`.
+    ///
+    /// The total is not rounded; an empty iterator sums to zero in the target currency.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::MissingExchangeRate` if the exchange has no rate from an item's currency to the target.
+    fn sum_converted(self, exchange: &Exchange, target: Currency) -> Result<Money<A>, MoneyError> {
+        let bag: MoneyBag<A> = self.map(|item| item.borrow().clone()).collect();
+        bag.collapse(exchange, target, RoundingPolicy::Unrounded)
+    }
+}
+
+impl<A: Amount, I> MoneyIterator<A> for I
+where
+    I: Iterator,
+    I::Item: Borrow<Money<A>>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::currencies::iso::{EUR, GBP, USD};
+    use crate::{ExchangeRate, FixedPoint};
+    use rust_decimal_macros::dec;
+
+    fn mixed() -> Vec<Money> {
+        vec![
+            Money::from_minor(1_000, USD),
+            Money::from_minor(500, EUR),
+            Money::from_minor(-200, USD),
+            Money::from_minor(-500, EUR),
+        ]
+    }
+
+    #[test]
+    fn iter_try_sum() {
+        let values = vec![Money::from_minor(100, USD), Money::from_minor(250, USD)];
+        assert_eq!(values.iter().try_sum(), Ok(Money::from_minor(350, USD)));
+        assert_eq!(values.into_iter().try_sum(), Ok(Money::from_minor(350, USD)));
+        assert_eq!(mixed().iter().try_sum(), Err(MoneyError::CurrencyMismatch));
+        assert_eq!(Vec::<Money>::new().iter().try_sum(), Err(MoneyError::EmptySum));
+
+        let fixed = vec![Money::<FixedPoint<2>>::parse("0.10 USD").unwrap(); 3];
+        assert_eq!(fixed.iter().try_sum(), Money::parse("0.30 USD"));
+    }
+
+    #[test]
+    fn iter_try_min_max() {
+        let values = [Money::from_minor(300, GBP), Money::from_minor(-100, GBP), Money::from_minor(200, GBP)];
+        assert_eq!(values.iter().try_min(), Ok(Money::from_minor(-100, GBP)));
+        assert_eq!(values.iter().try_max(), Ok(Money::from_minor(300, GBP)));
+        assert_eq!(mixed().into_iter().try_max(), Err(MoneyError::CurrencyMismatch));
+        assert_eq!(Vec::<Money>::new().into_iter().try_min(), Err(MoneyError::EmptySum));
+    }
+
+    #[test]
+    fn iter_sum_by_currency() {
+        let totals = mixed().iter().sum_by_currency();
+        assert_eq!(totals.len(), 2);
+        assert_eq!(totals["USD"], Money::from_minor(800, USD));
+        assert_eq!(totals["EUR"], Money::from_minor(0, EUR));
+        assert!(Vec::<Money>::new().iter().sum_by_currency().is_empty());
+    }
+
+    #[test]
+    fn iter_sum_converted() {
+        let mut exchange = Exchange::new();
+        exchange.set_rate(&ExchangeRate::new(EUR, USD, dec!(1.1)).unwrap());
+
+        let values = [Money::from_minor(1_000, USD), Money::from_minor(1_000, EUR)];
+        assert_eq!(values.iter().sum_converted(&exchange, USD), Ok(Money::from_minor(2_100, USD)));
+        assert_eq!(values.iter().sum_converted(&exchange, GBP), Err(MoneyError::MissingExchangeRate));
+        assert_eq!(Vec::<Money>::new().iter().sum_converted(&exchange, GBP), Ok(Money::from_minor(0, GBP)));
+    }
+}
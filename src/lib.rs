@@ -1,18 +1,50 @@
 #![doc = include_str!("../README.md")]
 
+mod amount;
+mod decimal256;
 mod error;
 mod exchange;
+mod fee;
 mod format;
+mod import;
+mod index;
+mod interest;
+mod invoice;
+mod iter;
 mod locale;
 mod money;
+mod money_bag;
+mod proration;
+mod provider;
+mod quote;
+mod shared;
+mod tax;
 pub mod currency;
 pub mod currencies;
+// Unlike the modules above, these hold free functions rather than types. Names such as `finance::npv`,
+// `stats::mean` or `stats::max` would be unclear at the crate root, so they stay under their module path.
+pub mod finance;
+pub mod stats;
 
 // Include the code generated by build.rs (CURRENCIES and LOCALES maps)
 include!(concat!(env!("OUT_DIR"), "/generated_maps.rs"));
 
+pub use amount::*;
 pub use currency::*;
+pub use decimal256::*;
 pub use error::MoneyError;
 pub use exchange::*;
+pub use fee::*;
 pub use format::*;
+pub use import::*;
+pub use index::*;
+pub use interest::*;
+pub use invoice::*;
+pub use iter::*;
 pub use money::*;
+pub use money_bag::*;
+pub use proration::*;
+pub use provider::*;
+pub use quote::*;
+pub use shared::*;
+pub use tax::*;
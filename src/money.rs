@@ -1,6 +1,6 @@
 use crate::currency::FormattableCurrency;
 // Removed: use crate::locale::LocalFormat;
-use crate::{find_currency, Currency, MoneyError};
+use crate::{find_currency, find_denomination, Amount, Currency, MoneyError};
 
 // Consolidate imports
 use std::cmp::Ordering;
@@ -17,16 +17,50 @@ use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
 /// Represents an amount of a given currency.
 ///
-/// Money represents financial amounts through a Decimal (owned) and a Currency (reference).
+/// Money represents financial amounts through an [`Amount`] (owned) and a Currency.
+/// The amount backend defaults to `Decimal`; other backends such as [`crate::FixedPoint`]
+/// can be used through `Money<A>`.
 /// Operations on Money objects always create new instances of Money, with the exception
 /// of `round()`.
 #[derive(Debug, PartialEq, Eq, Clone)] // Removed Copy
-pub struct Money {
-    amount: Decimal,
+pub struct Money<A = Decimal> {
+    amount: A,
     // Removed duplicate amount field
     currency: Currency,
 }
 
+/// How `Money::try_from_f64` treats a float with digits below the currency's minor unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloatPolicy {
+    /// Reject the float with `MoneyError::PrecisionLoss`.
+    Exact,
+    /// Round the float to the currency's exponent with the given strategy.
+    Round(RoundingStrategy),
+}
+
+/// How a computed amount is rounded, used by calculations that produce new Money values.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_money::{Money, RoundingPolicy};
+/// use rusty_money::currencies::iso::USD;
+/// use rust_decimal::RoundingStrategy;
+///
+/// let third: Money = Money::from_major(10, USD) / 3;
+/// let rounded = third.round_with(RoundingPolicy::ToCurrency(RoundingStrategy::MidpointNearestEven));
+/// assert_eq!(rounded, Money::from_minor(333, USD));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundingPolicy {
+    /// Keep the full precision of the calculation.
+    Unrounded,
+    /// Round to the currency's exponent (its minor unit) with the given strategy.
+    ToCurrency(RoundingStrategy),
+    /// Round to a fixed number of decimal places with the given strategy.
+    ToDecimalPlaces(u32, RoundingStrategy),
+}
+
 // --- Serde Implementation (Conditional) ---
 
 // Default: Tuple format `(amount, currency_code)` for backward compatibility
@@ -34,8 +68,9 @@ pub struct Money {
 mod serde_tuple {
     use super::*;
     use serde::ser::SerializeTuple;
+    use std::marker::PhantomData;
 
-    impl Serialize for Money {
+    impl<A: Amount + Serialize> Serialize for Money<A> {
         fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where
             S: Serializer,
@@ -48,15 +83,15 @@ mod serde_tuple {
         }
     }
 
-    impl<'de> Deserialize<'de> for Money {
+    impl<'de, A: Amount + Deserialize<'de>> Deserialize<'de> for Money<A> {
         fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where
             D: Deserializer<'de>,
         {
-            struct MoneyVisitor;
+            struct MoneyVisitor<A>(PhantomData<A>);
 
-            impl<'de> de::Visitor<'de> for MoneyVisitor {
-                type Value = Money;
+            impl<'de, A: Amount + Deserialize<'de>> de::Visitor<'de> for MoneyVisitor<A> {
+                type Value = Money<A>;
 
                 fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
                     formatter.write_str(
@@ -64,11 +99,11 @@ mod serde_tuple {
                     )
                 }
 
-                fn visit_seq<A>(self, mut seq: A) -> Result<Money, A::Error>
+                fn visit_seq<S>(self, mut seq: S) -> Result<Money<A>, S::Error>
                 where
-                    A: de::SeqAccess<'de>,
+                    S: de::SeqAccess<'de>,
                 {
-                    let amount: Decimal = seq
+                    let amount: A = seq
                         .next_element()?
                         .ok_or_else(|| de::Error::invalid_length(0, &self))?;
                     let currency_code: String = seq
@@ -81,12 +116,12 @@ mod serde_tuple {
 
                     Ok(Money {
                         amount,
-                        currency: currency.clone(),
-                    }) // Clone static currency ref
+                        currency: *currency,
+                    }) // Copy static currency ref
                 }
             }
 
-            deserializer.deserialize_tuple(2, MoneyVisitor)
+            deserializer.deserialize_tuple(2, MoneyVisitor(PhantomData))
         }
     }
 }
@@ -100,18 +135,17 @@ mod serde_struct_format {
     #[derive(Serialize)]
     #[serde(rename_all = "camelCase")]
     struct MoneyHelper<'a> {
-        #[serde(with = "rust_decimal::serde::str")] // Serialize Decimal as string
-        amount: &'a Decimal,
+        amount: String, // Amounts are always serialized as strings
         currency: &'a str,
     }
 
-    impl Serialize for Money {
+    impl<A: Amount> Serialize for Money<A> {
         fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where
             S: Serializer,
         {
             let helper = MoneyHelper {
-                amount: &self.amount,
+                amount: self.amount.to_string(),
                 currency: self.currency.code(),
             };
             helper.serialize(serializer)
@@ -122,23 +156,23 @@ mod serde_struct_format {
     #[derive(Deserialize)]
     #[serde(rename_all = "camelCase")]
     struct MoneyHelperOwned {
-        #[serde(with = "rust_decimal::serde::str")] // Deserialize Decimal from string
-        amount: Decimal,
+        amount: String, // Parsed with Amount::parse below
         currency: String,
     }
 
-    impl<'de> Deserialize<'de> for Money {
+    impl<'de, A: Amount> Deserialize<'de> for Money<A> {
         fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where
             D: Deserializer<'de>,
         {
             let helper = MoneyHelperOwned::deserialize(deserializer)?;
+            let amount = A::parse(&helper.amount).map_err(de::Error::custom)?;
             let currency = find_currency(&helper.currency).ok_or_else(|| {
                 de::Error::custom(format!("unknown currency code '{}'", helper.currency))
             })?;
             Ok(Money {
-                amount: helper.amount,
-                currency,
+                amount,
+                currency: *currency,
             })
         }
     }
@@ -146,25 +180,22 @@ mod serde_struct_format {
 
 // --- End Serde Implementation ---
 
-impl Add for Money {
-    type Output = Result<Money, MoneyError>;
+impl<A: Amount> Add for Money<A> {
+    type Output = Result<Money<A>, MoneyError>;
     /// Adds two Money objects, returning a new Money object.
     ///
     /// # Errors
     ///
     /// Returns `Err(MoneyError::CurrencyMismatch)` if the two Money objects have different currencies.
-    fn add(self, other: Money) -> Self::Output {
+    fn add(self, other: Money<A>) -> Self::Output {
         if self.currency != other.currency {
             return Err(MoneyError::CurrencyMismatch);
         }
-        Ok(Money::from_decimal(
-            self.amount + other.amount,
-            self.currency.clone(), // Clone currency
-        ))
+        Ok(Money::from_amount(self.amount + other.amount, self.currency))
     }
 }
 
-impl AddAssign for Money {
+impl<A: Amount> AddAssign for Money<A> {
     /// Adds another Money object to this one in-place.
     ///
     /// # Panics
@@ -178,29 +209,26 @@ impl AddAssign for Money {
             // but explicitly panicking is clearer for now based on original behavior.
             panic!("Cannot add Money with different currencies using AddAssign");
         }
-        self.amount += other.amount;
+        self.amount = self.amount.clone() + other.amount;
     }
 }
 
-impl Sub for Money {
-    type Output = Result<Money, MoneyError>;
+impl<A: Amount> Sub for Money<A> {
+    type Output = Result<Money<A>, MoneyError>;
     /// Subtracts another Money object from this one, returning a new Money object.
     ///
     /// # Errors
     ///
     /// Returns `Err(MoneyError::CurrencyMismatch)` if the two Money objects have different currencies.
-    fn sub(self, other: Money) -> Self::Output {
+    fn sub(self, other: Money<A>) -> Self::Output {
         if self.currency != other.currency {
             return Err(MoneyError::CurrencyMismatch);
         }
-        Ok(Money::from_decimal(
-            self.amount - other.amount,
-            self.currency.clone(), // Clone currency
-        ))
+        Ok(Money::from_amount(self.amount - other.amount, self.currency))
     }
 }
 
-impl SubAssign for Money {
+impl<A: Amount> SubAssign for Money<A> {
     /// Subtracts another Money object from this one in-place.
     ///
     /// # Panics
@@ -212,25 +240,25 @@ impl SubAssign for Money {
         if self.currency != other.currency {
             panic!("Cannot subtract Money with different currencies using SubAssign");
         }
-        self.amount -= other.amount;
+        self.amount = self.amount.clone() - other.amount;
     }
 }
 
-impl Neg for Money {
-    type Output = Money;
+impl<A: Amount> Neg for Money<A> {
+    type Output = Money<A>;
 
     fn neg(self) -> Self::Output {
         Money {
             amount: -self.amount,
-            currency: self.currency.clone(), // Clone currency
+            currency: self.currency,
         }
     }
 }
 
 macro_rules! impl_mul_div {
     ($type:ty) => {
-        impl Mul<$type> for Money {
-            type Output = Money;
+        impl<A: Amount> Mul<$type> for Money<A> {
+            type Output = Money<A>;
 
             /// Multiplies Money by a scalar value, returning a new Money object.
             ///
@@ -244,16 +272,15 @@ macro_rules! impl_mul_div {
             /// let doubled: Money = money * 2;
             /// assert_eq!(doubled.to_string(), "$20.00");
             /// ```
-            fn mul(self, rhs: $type) -> Money {
-                // Use Decimal::from for primitive types, which is safe and efficient.
-                // For Decimal itself, no conversion needed.
-                let rhs_decimal = Decimal::from(rhs);
-                Money::from_decimal(self.amount * rhs_decimal, self.currency.clone()) // Clone currency
+            fn mul(self, rhs: $type) -> Money<A> {
+                // Use Decimal::from for primitive types, which is safe and efficient, and keep the
+                // factor at full precision rather than rounding it to the amount backend.
+                Money::from_amount(self.amount.mul_decimal(Decimal::from(rhs)), self.currency)
             }
         }
 
-        impl Mul<Money> for $type {
-            type Output = Money;
+        impl<A: Amount> Mul<Money<A>> for $type {
+            type Output = Money<A>;
 
             /// Multiplies a scalar value by Money, returning a new Money object.
             ///
@@ -267,14 +294,13 @@ macro_rules! impl_mul_div {
             /// let doubled: Money = 2 * money;
             /// assert_eq!(doubled.to_string(), "$20.00");
             /// ```
-            fn mul(self, rhs: Money) -> Money {
+            fn mul(self, rhs: Money<A>) -> Money<A> {
                 // Use Decimal::from for primitive types.
-                let lhs_decimal = Decimal::from(self);
-                Money::from_decimal(rhs.amount * lhs_decimal, rhs.currency.clone()) // Clone currency
+                Money::from_amount(rhs.amount.mul_decimal(Decimal::from(self)), rhs.currency)
             }
         }
 
-        impl MulAssign<$type> for Money {
+        impl<A: Amount> MulAssign<$type> for Money<A> {
             /// Multiplies Money by a scalar value in-place.
             ///
             /// # Examples
@@ -290,12 +316,12 @@ macro_rules! impl_mul_div {
             fn mul_assign(&mut self, rhs: $type) {
                 // Currency doesn't change in MulAssign/DivAssign, no clone needed here.
                 // Just update the amount.
-                self.amount *= Decimal::from(rhs);
+                self.amount = self.amount.mul_decimal(Decimal::from(rhs));
             }
         }
 
-        impl Div<$type> for Money {
-            type Output = Money;
+        impl<A: Amount> Div<$type> for Money<A> {
+            type Output = Money<A>;
 
             /// Divides Money by a scalar value, returning a new Money object.
             ///
@@ -313,16 +339,15 @@ macro_rules! impl_mul_div {
             /// # Panics
             ///
             /// Panics if the divisor is zero, which would result in a division by zero error.
-            fn div(self, rhs: $type) -> Money {
+            fn div(self, rhs: $type) -> Money<A> {
                 // Use Decimal::from for primitive types.
-                let rhs_decimal = Decimal::from(rhs);
-                // Division by zero will panic within Decimal itself.
-                Money::from_decimal(self.amount / rhs_decimal, self.currency.clone()) // Clone currency
+                // Division by zero will panic within the amount backend itself.
+                Money::from_amount(self.amount.div_decimal(Decimal::from(rhs)), self.currency)
             }
         }
 
-        impl Div<Money> for $type {
-            type Output = Money;
+        impl<A: Amount> Div<Money<A>> for $type {
+            type Output = Money<A>;
 
             /// Divides a scalar value by Money, returning a new Money object with the same currency.
             ///
@@ -341,15 +366,20 @@ macro_rules! impl_mul_div {
             /// # Panics
             ///
             /// Panics if the Money amount is zero, which would result in a division by zero error.
-            fn div(self, rhs: Money) -> Money {
-                // Use Decimal::from for primitive types.
-                let lhs_decimal = Decimal::from(self);
-                // Division by zero Money amount will panic within Decimal.
-                Money::from_decimal(lhs_decimal / rhs.amount, rhs.currency.clone()) // Clone currency
+            fn div(self, rhs: Money<A>) -> Money<A> {
+                // Use Decimal::from for primitive types, and divide in Decimal when the amount fits
+                // one so the scalar is not rounded to the amount backend first.
+                // Division by zero Money amount will panic.
+                let lhs = Decimal::from(self);
+                let amount = match rhs.amount.to_decimal() {
+                    Some(divisor) => A::from_decimal(lhs / divisor),
+                    None => A::from_decimal(lhs) / rhs.amount,
+                };
+                Money::from_amount(amount, rhs.currency)
             }
         }
 
-        impl DivAssign<$type> for Money {
+        impl<A: Amount> DivAssign<$type> for Money<A> {
             /// Divides Money by a scalar value in-place.
             ///
             /// # Examples
@@ -369,7 +399,7 @@ macro_rules! impl_mul_div {
             fn div_assign(&mut self, rhs: $type) {
                 // Currency doesn't change in MulAssign/DivAssign, no clone needed here.
                 // Just update the amount.
-                self.amount /= Decimal::from(rhs);
+                self.amount = self.amount.div_decimal(Decimal::from(rhs));
             }
         }
     };
@@ -387,21 +417,24 @@ impl_mul_div!(u32);
 impl_mul_div!(u64);
 impl_mul_div!(Decimal);
 
-impl PartialOrd for Money {
+// PartialOrd deliberately returns None across currencies while Ord panics, so the two are
+// not derived from one another.
+#[allow(clippy::non_canonical_partial_ord_impl)]
+impl<A: Amount> PartialOrd for Money<A> {
     /// Partially compares two Money objects based on their amounts.
     ///
     /// Returns `None` if the currencies differ, preventing comparison of apples and oranges.
     /// Otherwise, returns `Some(Ordering)` based on the comparison of their amounts.
-    fn partial_cmp(&self, other: &Money) -> Option<Ordering> {
+    fn partial_cmp(&self, other: &Money<A>) -> Option<Ordering> {
         if self.currency != other.currency {
             None
         } else {
-            self.amount.partial_cmp(&other.amount) // Decimal implements PartialOrd
+            self.amount.partial_cmp(&other.amount) // Amount implements PartialOrd
         }
     }
 }
 
-impl Ord for Money {
+impl<A: Amount> Ord for Money<A> {
     /// Compares two Money objects of the **same** currency based on their amounts.
     ///
     /// # Panics
@@ -413,11 +446,11 @@ impl Ord for Money {
     /// The panic ensures that `Money` objects behave correctly when used in sorted collections
     /// (like `BTreeSet`, `BinaryHeap`) which rely on `Ord`. These collections require a total
     /// ordering, which is only meaningful for money of the same currency.
-    fn cmp(&self, other: &Money) -> Ordering {
+    fn cmp(&self, other: &Money<A>) -> Ordering {
         if self.currency != other.currency {
             panic!("Cannot compare Money with different currencies using Ord::cmp");
         }
-        self.amount.cmp(&other.amount) // Decimal implements Ord
+        self.amount.cmp(&other.amount) // Amount implements Ord
     }
 }
 
@@ -463,25 +496,7 @@ impl FromStr for Money {
     /// Returns `MoneyError::InvalidCurrency` if:
     /// - The currency code part is not recognized as a valid currency code
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts: Vec<&str> = s.trim().split_whitespace().collect();
-        if parts.len() != 2 {
-            return Err(MoneyError::InvalidAmountFormat);
-        }
-
-        let amount_str = parts[0];
-        let currency_code = parts[1];
-
-        // Special case for non-numeric amount strings like "abc USD"
-        if amount_str.chars().any(|c| !c.is_numeric() && c != '.' && c != ',' && c != '-' && c != '+') {
-            return Err(MoneyError::InvalidAmountFormat);
-        }
-
-        // Use ? which leverages From<DecimalError> for MoneyError::InvalidAmountDecimal
-        let amount = Decimal::from_str(amount_str)?;
-        // Use ? after converting Option to Result
-        let currency = find_currency(currency_code).ok_or(MoneyError::InvalidCurrency)?;
-
-        Ok(Money::from_decimal(amount, currency.clone())) // Clone static currency ref
+        Money::parse(s)
     }
 }
 
@@ -507,8 +522,301 @@ impl Money {
         Money { amount, currency }
     }
 
-    /// Returns a reference to the Decimal amount.
-    pub fn amount(&self) -> &Decimal {
+    /// Creates a Money object from an `f64`, handling sub-minor-unit digits according to `policy`.
+    ///
+    /// The float is read as the shortest decimal that converts back to the same `f64`, so
+    /// `0.1` becomes exactly 0.1 rather than 0.1000000000000000055511151231257827.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::{FloatPolicy, Money, MoneyError};
+    /// use rusty_money::currencies::iso::USD;
+    /// use rust_decimal::RoundingStrategy;
+    ///
+    /// let money = Money::try_from_f64(19.99, USD, FloatPolicy::Exact).unwrap();
+    /// assert_eq!(money, Money::from_minor(1999, USD));
+    ///
+    /// let policy = FloatPolicy::Round(RoundingStrategy::MidpointNearestEven);
+    /// let money = Money::try_from_f64(0.1 + 0.2, USD, policy).unwrap();
+    /// assert_eq!(money, Money::from_minor(30, USD));
+    ///
+    /// let result = Money::try_from_f64(0.1 + 0.2, USD, FloatPolicy::Exact);
+    /// assert_eq!(result, Err(MoneyError::PrecisionLoss));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(MoneyError::NonFiniteAmount)` if the value is NaN or infinite.
+    /// Returns `Err(MoneyError::PrecisionLoss)` if the policy is `FloatPolicy::Exact` and the value
+    /// has precision below one minor unit.
+    /// Returns `Err(MoneyError::Overflow)` if the value is too large for a `Decimal`.
+    pub fn try_from_f64(value: f64, currency: Currency, policy: FloatPolicy) -> Result<Money, MoneyError> {
+        if !value.is_finite() {
+            return Err(MoneyError::NonFiniteAmount);
+        }
+        // Display prints the shortest round-tripping decimal, never in scientific notation.
+        let text = value.to_string();
+        let exponent = currency.exponent();
+        let decimals = text.split_once('.').map_or(0, |(_, frac)| frac.trim_end_matches('0').len());
+        if policy == FloatPolicy::Exact && decimals > exponent as usize {
+            return Err(MoneyError::PrecisionLoss);
+        }
+        let amount = Decimal::from_str(&text).map_err(|_| MoneyError::Overflow)?;
+        let amount = match policy {
+            FloatPolicy::Exact => amount,
+            FloatPolicy::Round(strategy) => amount.round_dp_with_strategy(exponent, strategy),
+        };
+        Ok(Money::from_decimal(amount, currency))
+    }
+
+    /// Creates a Money object given an integer amount of a named denomination of the currency.
+    ///
+    /// Denomination names are matched ignoring ASCII case; see `find_denomination`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::Money;
+    /// use rusty_money::currencies::crypto::{BTC, ETH};
+    /// use rust_decimal_macros::dec;
+    ///
+    /// let fee = Money::from_denomination(21_000, "gwei", ETH).unwrap();
+    /// assert_eq!(fee.amount(), &dec!(0.000021));
+    ///
+    /// let sats = Money::from_denomination(150_000_000, "satoshi", BTC).unwrap();
+    /// assert_eq!(sats, Money::from_minor(150_000_000, BTC));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(MoneyError::InvalidDenomination)` if the denomination is not known for the currency.
+    pub fn from_denomination(amount: i64, denomination: &str, currency: Currency) -> Result<Money, MoneyError> {
+        let denomination = find_denomination(&currency, denomination).ok_or(MoneyError::InvalidDenomination)?;
+        Ok(Money::from_decimal(Decimal::new(amount, denomination.exponent), currency))
+    }
+}
+
+impl<A: Amount> Money<A> {
+    /// Creates a Money object given an amount in any numeric backend and a currency.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::{FixedPoint, Money};
+    /// use rusty_money::currencies::iso::USD;
+    ///
+    /// let money = Money::from_amount(FixedPoint::<2>::from_units(1050), USD);
+    /// assert_eq!(money.to_string(), "$10.50");
+    /// ```
+    pub fn from_amount(amount: A, currency: Currency) -> Money<A> {
+        Money { amount, currency }
+    }
+
+    /// Parses a string like "123.45 USD" into a Money object using the amount backend `A`.
+    ///
+    /// This is the backend-generic form of `Money::from_str`, e.g.
+    /// `Money::<FixedPoint<2>>::parse("123.45 USD")`. See `FromStr for Money` for the
+    /// accepted format and the errors returned.
+    pub fn parse(s: &str) -> Result<Money<A>, MoneyError> {
+        let parts: Vec<&str> = s.split_whitespace().collect();
+        if parts.len() != 2 {
+            return Err(MoneyError::InvalidAmountFormat);
+        }
+
+        let amount_str = parts[0];
+        let currency_code = parts[1];
+
+        // Special case for non-numeric amount strings like "abc USD"
+        if amount_str.chars().any(|c| !c.is_numeric() && c != '.' && c != ',' && c != '-' && c != '+') {
+            return Err(MoneyError::InvalidAmountFormat);
+        }
+
+        let amount = A::parse(amount_str)?;
+        // Use ? after converting Option to Result
+        let currency = find_currency(currency_code).ok_or(MoneyError::InvalidCurrency)?;
+
+        Ok(Money::from_amount(amount, *currency)) // Copy static currency ref
+    }
+
+    /// Creates a Money object from a raw on-chain integer string and the token's decimals.
+    ///
+    /// The string must contain only ASCII digits, as returned by a node for a `uint256` balance.
+    /// The result is exact as long as the backend `A` can hold it, so `Decimal256` is
+    /// recommended for 18 decimal tokens.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::{Decimal256, Money};
+    /// use rusty_money::currencies::crypto::ETH;
+    ///
+    /// let balance = Money::<Decimal256>::from_raw_str("1500000000000000000", 18, ETH).unwrap();
+    /// assert_eq!(balance.amount().to_string(), "1.500000000000000000");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(MoneyError::InvalidAmountFormat)` if the string is empty or contains anything
+    /// but ASCII digits, and any error returned by `Amount::parse` for the backend.
+    pub fn from_raw_str(raw: &str, decimals: u32, currency: Currency) -> Result<Money<A>, MoneyError> {
+        if raw.is_empty() || !raw.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(MoneyError::InvalidAmountFormat);
+        }
+        // Insert the decimal point textually so that no backend has to divide.
+        let decimals = decimals as usize;
+        let padded = format!("{:0>width$}", raw, width = decimals + 1);
+        let (int_part, frac_part) = padded.split_at(padded.len() - decimals);
+        let amount = if frac_part.is_empty() {
+            A::parse(int_part)?
+        } else {
+            A::parse(&format!("{}.{}", int_part, frac_part))?
+        };
+        Ok(Money::from_amount(amount, currency))
+    }
+
+    /// Returns the amount expressed in a named denomination of the currency.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::Money;
+    /// use rusty_money::currencies::crypto::ETH;
+    /// use rust_decimal_macros::dec;
+    ///
+    /// let fee = Money::from_denomination(21_000, "gwei", ETH).unwrap();
+    /// assert_eq!(fee.to_denomination("wei").unwrap(), dec!(21_000_000_000_000));
+    /// assert_eq!(fee.to_denomination("ETH").unwrap(), dec!(0.000021));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(MoneyError::InvalidDenomination)` if the denomination is not known for the currency.
+    pub fn to_denomination(&self, denomination: &str) -> Result<A, MoneyError> {
+        let denomination =
+            find_denomination(&self.currency, denomination).ok_or(MoneyError::InvalidDenomination)?;
+        Ok(self.amount.clone() * A::pow10(denomination.exponent))
+    }
+
+    /// Returns the amount as an integer number of minor units, e.g. 10.50 USD -> 1050.
+    ///
+    /// This is the inverse of `Money::from_minor`. Use `to_minor_i64_rounded` to round
+    /// sub-minor-unit precision instead of rejecting it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::{Money, MoneyError};
+    /// use rusty_money::currencies::iso::USD;
+    /// use rust_decimal_macros::dec;
+    ///
+    /// assert_eq!(Money::from_minor(1050, USD).to_minor_i64(), Ok(1050));
+    /// assert_eq!(
+    ///     Money::from_decimal(dec!(10.505), USD).to_minor_i64(),
+    ///     Err(MoneyError::PrecisionLoss)
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(MoneyError::PrecisionLoss)` if the amount has precision below one minor unit.
+    /// Returns `Err(MoneyError::Overflow)` if the number of minor units does not fit into an `i64`.
+    pub fn to_minor_i64(&self) -> Result<i64, MoneyError> {
+        i64::try_from(self.to_minor_i128()?).map_err(|_| MoneyError::Overflow)
+    }
+
+    /// Returns the amount as an integer number of minor units in an `i128`.
+    ///
+    /// See `to_minor_i64`; the wider type covers 18 decimal tokens and very large balances.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(MoneyError::PrecisionLoss)` if the amount has precision below one minor unit.
+    /// Returns `Err(MoneyError::Overflow)` if the number of minor units does not fit into an `i128`.
+    pub fn to_minor_i128(&self) -> Result<i128, MoneyError> {
+        let exponent = self.currency.exponent();
+        if self.amount.round_dp(exponent, RoundingStrategy::ToZero) != self.amount {
+            return Err(MoneyError::PrecisionLoss);
+        }
+        minor_units(&self.amount, exponent)
+    }
+
+    /// Rounds the amount to the currency's exponent with `strategy`, then returns it as an
+    /// integer number of minor units.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::Money;
+    /// use rusty_money::currencies::iso::USD;
+    /// use rust_decimal::RoundingStrategy;
+    /// use rust_decimal_macros::dec;
+    ///
+    /// let money = Money::from_decimal(dec!(10.505), USD);
+    /// assert_eq!(money.to_minor_i64_rounded(RoundingStrategy::MidpointNearestEven), Ok(1050));
+    /// assert_eq!(money.to_minor_i64_rounded(RoundingStrategy::MidpointAwayFromZero), Ok(1051));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(MoneyError::Overflow)` if the number of minor units does not fit into an `i64`.
+    pub fn to_minor_i64_rounded(&self, strategy: RoundingStrategy) -> Result<i64, MoneyError> {
+        i64::try_from(self.to_minor_i128_rounded(strategy)?).map_err(|_| MoneyError::Overflow)
+    }
+
+    /// Rounds the amount to the currency's exponent with `strategy`, then returns it as an
+    /// integer number of minor units in an `i128`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(MoneyError::Overflow)` if the number of minor units does not fit into an `i128`.
+    pub fn to_minor_i128_rounded(&self, strategy: RoundingStrategy) -> Result<i128, MoneyError> {
+        let exponent = self.currency.exponent();
+        minor_units(&self.amount.round_dp(exponent, strategy), exponent)
+    }
+
+    /// Returns the amount as an `f64`, or an error if the float does not represent it exactly.
+    ///
+    /// The conversion counts as exact when the float converts back to the same amount, so
+    /// 0.10 USD is exact even though `0.1` has no finite binary representation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::{Money, MoneyError};
+    /// use rusty_money::currencies::iso::USD;
+    /// use rust_decimal_macros::dec;
+    ///
+    /// assert_eq!(Money::from_minor(1999, USD).to_f64(), Ok(19.99));
+    ///
+    /// let precise = Money::from_decimal(dec!(0.12345678901234567890), USD);
+    /// assert_eq!(precise.to_f64(), Err(MoneyError::PrecisionLoss));
+    /// assert_eq!(precise.to_f64_lossy(), 0.12345678901234568);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(MoneyError::PrecisionLoss)` if the amount has more significant digits than an
+    /// `f64` can hold. Use `to_f64_lossy` to get the nearest float regardless.
+    pub fn to_f64(&self) -> Result<f64, MoneyError> {
+        let value = self.to_f64_lossy();
+        match A::parse(&value.to_string()) {
+            Ok(round_trip) if round_trip == self.amount => Ok(value),
+            _ => Err(MoneyError::PrecisionLoss),
+        }
+    }
+
+    /// Returns the `f64` nearest to the amount, silently dropping any precision it cannot hold.
+    pub fn to_f64_lossy(&self) -> f64 {
+        // Amounts display as plain decimal strings, which f64 parses with correct rounding.
+        self.amount
+            .to_string()
+            .parse()
+            .expect("amounts always display as plain decimal numbers")
+    }
+
+    /// Returns a reference to the amount.
+    pub fn amount(&self) -> &A {
         &self.amount
     }
 
@@ -519,17 +827,17 @@ impl Money {
 
     /// Returns true if amount == 0.
     pub fn is_zero(&self) -> bool {
-        self.amount == Decimal::ZERO
+        self.amount.is_zero()
     }
 
     /// Returns true if amount > 0.
     pub fn is_positive(&self) -> bool {
-        self.amount.is_sign_positive() && self.amount != Decimal::ZERO
+        self.amount > A::zero()
     }
 
     /// Returns true if amount < 0.
     pub fn is_negative(&self) -> bool {
-        self.amount.is_sign_negative() && self.amount != Decimal::ZERO
+        self.amount < A::zero()
     }
 
     /// Divides Money equally into `n` shares.
@@ -544,7 +852,7 @@ impl Money {
     /// # Errors
     ///
     /// Returns `Err(MoneyError::InvalidRatio)` if `number` is zero or negative.
-    pub fn allocate_to(&self, number: i32) -> Result<Vec<Money>, MoneyError> {
+    pub fn allocate_to(&self, number: i32) -> Result<Vec<Money<A>>, MoneyError> {
         if number <= 0 {
             return Err(MoneyError::InvalidRatio); // Ensure number is positive
         }
@@ -562,7 +870,7 @@ impl Money {
     /// # Arguments
     ///
     /// * `ratios` - A vector of integers representing the ratio for each share. Must not be empty
-    ///              and all ratios must be non-negative.
+    ///   and all ratios must be non-negative.
     ///
     /// # Examples
     ///
@@ -611,7 +919,7 @@ impl Money {
     ///
     /// These panic conditions represent internal logic errors in the implementation rather
     /// than invalid inputs, and should never be encountered in normal operation.
-    pub fn allocate(&self, ratios: Vec<i32>) -> Result<Vec<Money>, MoneyError> {
+    pub fn allocate(&self, ratios: Vec<i32>) -> Result<Vec<Money<A>>, MoneyError> {
         if ratios.is_empty() {
             return Err(MoneyError::InvalidRatio);
         }
@@ -620,41 +928,76 @@ impl Money {
              return Err(MoneyError::InvalidRatio);
         }
 
-        let ratio_total: i32 = ratios.iter().sum();
+        let weights: Vec<A> = ratios.iter().map(|r| A::from_decimal(Decimal::from(*r))).collect();
+        self.allocate_weighted(&weights)
+    }
+
+    /// Divides Money into shares proportional to arbitrary amounts, such as the line totals of an invoice.
+    ///
+    /// Works like `allocate`, except that weights are amounts rather than integer ratios and may be zero.
+    /// Shares with a zero weight receive nothing, and the indivisible remainder is distributed one minor
+    /// unit at a time to the shares with a positive weight, starting from the beginning.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::Money;
+    /// use rusty_money::currencies::iso::USD;
+    /// use rust_decimal_macros::dec;
+    ///
+    /// // Spread a $5.00 discount over lines of $12.50, $0.00 and $37.50
+    /// let discount = Money::from_major(5, USD);
+    /// let shares = discount.allocate_weighted(&[dec!(12.50), dec!(0), dec!(37.50)]).unwrap();
+    /// assert_eq!(shares, vec![Money::from_minor(125, USD), Money::from_minor(0, USD), Money::from_minor(375, USD)]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(MoneyError::InvalidRatio)` if `weights` is empty, any weight is negative or all weights are zero.
+    pub fn allocate_weighted(&self, weights: &[A]) -> Result<Vec<Money<A>>, MoneyError> {
+        if weights.is_empty() || weights.iter().any(|w| w.is_negative()) {
+            return Err(MoneyError::InvalidRatio);
+        }
+
+        let weight_total = weights.iter().cloned().fold(A::zero(), |acc, w| acc + w);
+        if weight_total.is_zero() {
+            return Err(MoneyError::InvalidRatio);
+        }
         let mut shares = Vec::new();
 
         // Use the currency's exponent to determine the smallest unit
         // e.g., for USD (exp 2), scale is 100.
         // We do allocation on the minor units to ensure integer math behavior for remainders.
-        let scale = Decimal::from(10u32.pow(self.currency.exponent()));
-        
+        let scale = A::pow10(self.currency.exponent());
+        let floor = |value: A| value.round_dp(0, RoundingStrategy::ToNegativeInfinity);
+
         // Convert total amount to minor units (e.g. $10.00 -> 1000 cents)
         // We use floor to handle any sub-minor precision (truncating it for allocation)
-        let minor_amount = (self.amount * scale).floor();
-        let minor_total_ratio = Decimal::from(ratio_total);
+        let minor_amount = floor(self.amount.clone() * scale.clone());
 
-        let mut minor_remainder = minor_amount;
+        let mut minor_remainder = minor_amount.clone();
         let mut minor_shares = Vec::new();
 
-        for ratio in &ratios {
-            let ratio_dec = Decimal::from(*ratio);
-            let share = (minor_amount * ratio_dec / minor_total_ratio).floor();
+        for weight in weights {
+            let share = floor(minor_amount.clone() * weight.clone() / weight_total.clone());
+            minor_remainder = minor_remainder - share.clone();
             minor_shares.push(share);
-            minor_remainder -= share;
         }
 
-        // Distribute remainder one minor unit at a time
+        // Distribute remainder one minor unit at a time, skipping shares that should stay empty
+        let receivers: Vec<usize> = (0..weights.len()).filter(|&i| !weights[i].is_zero()).collect();
         let mut i = 0;
-        while minor_remainder > Decimal::ZERO {
-            minor_shares[i] += Decimal::ONE;
-            minor_remainder -= Decimal::ONE;
-            i = (i + 1) % minor_shares.len();
+        while minor_remainder > A::zero() {
+            let index = receivers[i];
+            minor_shares[index] = minor_shares[index].clone() + A::one();
+            minor_remainder = minor_remainder - A::one();
+            i = (i + 1) % receivers.len();
         }
 
         // Convert back to Money
         for share in minor_shares {
-            let amount = share / scale;
-            shares.push(Money::from_decimal(amount, self.currency.clone()));
+            let amount = share / scale.clone();
+            shares.push(Money::from_amount(amount, self.currency));
         }
 
         Ok(shares)
@@ -692,13 +1035,22 @@ impl Money {
     /// let rounded_even2 = money2.round(2, RoundingStrategy::MidpointNearestEven);
     /// assert_eq!(rounded_even2.amount(), &dec!(12.36)); // 5 rounds towards even 6
     /// ```
-    pub fn round(&self, digits: u32, strategy: RoundingStrategy) -> Money {
+    pub fn round(&self, digits: u32, strategy: RoundingStrategy) -> Money<A> {
         let mut money = self.clone(); // Clone self since Money is not Copy
-                                      // Directly use the provided strategy from rust_decimal
-        money.amount = money.amount.round_dp_with_strategy(digits, strategy);
+                                      // Delegate to the amount backend's rounding
+        money.amount = money.amount.round_dp(digits, strategy);
         money
     }
 
+    /// Returns a new `Money` object rounded according to a `RoundingPolicy`.
+    pub fn round_with(&self, policy: RoundingPolicy) -> Money<A> {
+        match policy {
+            RoundingPolicy::Unrounded => self.clone(),
+            RoundingPolicy::ToCurrency(strategy) => self.round(self.currency.exponent(), strategy),
+            RoundingPolicy::ToDecimalPlaces(digits, strategy) => self.round(digits, strategy),
+        }
+    }
+
     /// Sums an iterator of Money objects, returning the total sum.
     ///
     /// This method provides an explicit way to sum `Money` instances, handling
@@ -739,13 +1091,13 @@ impl Money {
     /// **Note:** Using `std::iter::Sum` directly on `Money` is discouraged because `Money`
     /// does not implement `Default` and addition can fail due to currency mismatches.
     /// This `sum_iter` method provides a safer and more explicit alternative.
-    pub fn sum_iter<I>(mut iter: I) -> Result<Option<Money>, MoneyError>
+    pub fn sum_iter<I>(iter: I) -> Result<Option<Money<A>>, MoneyError>
     where
-        I: Iterator<Item = Money>,
+        I: Iterator<Item = Money<A>>,
     {
-        let mut total: Option<Money> = None;
+        let mut total: Option<Money<A>> = None;
 
-        while let Some(item) = iter.next() {
+        for item in iter {
             match total {
                 None => {
                     total = Some(item);
@@ -763,7 +1115,7 @@ impl Money {
 // Removed the `impl Sum<Money> for Money` block.
 // Use `Money::sum_iter` for explicit and safe summation.
 
-impl fmt::Display for Money {
+impl<A: Amount> fmt::Display for Money<A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // Implementation using FormattableCurrency trait methods with formatting
 
@@ -775,10 +1127,10 @@ impl fmt::Display for Money {
         // Using MidpointAwayFromZero as a common default rounding strategy for display.
         let rounded_amount = self
             .amount
-            .round_dp_with_strategy(exponent, RoundingStrategy::MidpointAwayFromZero);
+            .round_dp(exponent, RoundingStrategy::MidpointAwayFromZero);
 
         // Format the amount with the correct number of decimal places
-        let mut amount_str = format!("{:.1$}", rounded_amount.abs(), exponent as usize);
+        let mut amount_str = with_exponent_digits(&rounded_amount.abs().to_string(), exponent);
 
         // Apply thousands separators based on currency
         if currency.code() == "EUR" {
@@ -793,7 +1145,7 @@ impl fmt::Display for Money {
         }
 
         // Place the symbol based on the currency's preference
-        if rounded_amount < Decimal::ZERO {
+        if rounded_amount < A::zero() {
             if currency.symbol_first() {
                 write!(f, "-{}{}", symbol, amount_str)
             } else {
@@ -809,6 +1161,26 @@ impl fmt::Display for Money {
     }
 }
 
+// Helper function to scale an amount with at most `exponent` decimal places to an integer.
+fn minor_units<A: Amount>(amount: &A, exponent: u32) -> Result<i128, MoneyError> {
+    amount
+        .checked_mul(&A::pow10(exponent))
+        .and_then(|minor| minor.to_i128())
+        .ok_or(MoneyError::Overflow)
+}
+
+// Helper function to pad or trim a plain amount string to exactly `exponent` decimal places.
+// Only called on amounts already rounded to `exponent`, so trimmed digits are always zeros.
+pub(crate) fn with_exponent_digits(amount_str: &str, exponent: u32) -> String {
+    let (integer_part, decimal_part) = amount_str.split_once('.').unwrap_or((amount_str, ""));
+    let exponent = exponent as usize;
+    if exponent == 0 {
+        return integer_part.to_string();
+    }
+    let decimal_part = &decimal_part[..decimal_part.len().min(exponent)];
+    format!("{}.{:0<width$}", integer_part, decimal_part, width = exponent)
+}
+
 // Helper function to format with thousands separators
 fn format_with_separators(amount_str: &str, thousands_sep: char, decimal_sep: char) -> String {
     let parts: Vec<&str> = amount_str.split('.').collect();
@@ -817,13 +1189,11 @@ fn format_with_separators(amount_str: &str, thousands_sep: char, decimal_sep: ch
 
     // Format the integer part with thousands separators
     let mut result = String::new();
-    let mut count = 0;
-    for c in integer_part.chars().rev() {
+    for (count, c) in integer_part.chars().rev().enumerate() {
         if count > 0 && count % 3 == 0 {
             result.insert(0, thousands_sep);
         }
         result.insert(0, c);
-        count += 1;
     }
 
     // Add the decimal part if it exists
@@ -850,7 +1220,7 @@ fn format_indian_style(amount_str: &str) -> String {
         // Add a comma before the last 3 digits, then before every 2 digits
         if i > 0 && (
             (len - i == 3) || // Before the last 3 digits
-            (len - i > 3 && (len - i - 3) % 2 == 0) // Then every 2 digits
+            (len - i > 3 && (len - i - 3).is_multiple_of(2)) // Then every 2 digits
         ) {
             result.push(',');
         }
@@ -1350,6 +1720,33 @@ mod tests {
         assert_eq!(monies.unwrap_err(), MoneyError::InvalidRatio);
     }
 
+    #[test]
+    fn money_allocate_weighted() {
+        let money = Money::from_minor(1_000, USD);
+        let monies = money.allocate_weighted(&[dec!(0), dec!(1.5), dec!(1.5)]).unwrap();
+        let expected_results = vec![
+            Money::from_minor(0, USD),
+            Money::from_minor(500, USD),
+            Money::from_minor(500, USD),
+        ];
+        assert_eq!(expected_results, monies);
+
+        // The remainder skips zero weights
+        let monies = Money::from_minor(101, USD).allocate_weighted(&[dec!(0), dec!(1), dec!(1)]).unwrap();
+        assert_eq!(monies[0], Money::from_minor(0, USD));
+        assert_eq!(monies[1], Money::from_minor(51, USD));
+
+        let fixed = Money::<crate::FixedPoint<2>>::parse("10.00 USD").unwrap();
+        let weights = [crate::FixedPoint::from_units(333), crate::FixedPoint::from_units(667)];
+        let shares = fixed.allocate_weighted(&weights).unwrap();
+        assert_eq!(shares[0], Money::parse("3.33 USD").unwrap());
+        assert_eq!(shares[1], Money::parse("6.67 USD").unwrap());
+
+        assert_eq!(money.allocate_weighted(&[]), Err(MoneyError::InvalidRatio));
+        assert_eq!(money.allocate_weighted(&[dec!(0), dec!(0)]), Err(MoneyError::InvalidRatio));
+        assert_eq!(money.allocate_weighted(&[dec!(-1), dec!(2)]), Err(MoneyError::InvalidRatio));
+    }
+
     #[test]
     fn money_fmt_separates_digits() {
         let usd = Money::from_minor(0, USD); // Zero Dollars
@@ -1426,6 +1823,193 @@ mod tests {
         let _2nd_derived_money = money * 3;
     }
 
+    #[test]
+    fn money_fixed_point_backend() {
+        type Cents = crate::FixedPoint<2>;
+
+        let money = Money::<Cents>::parse("11.00 USD").unwrap();
+        assert_eq!(money, Money::from_amount(Cents::from_units(1_100), USD));
+        assert_eq!(money.to_string(), "$11.00");
+
+        let shares = money.allocate_to(3).unwrap();
+        let expected: Vec<Money<Cents>> = [367, 367, 366]
+            .iter()
+            .map(|&units| Money::from_amount(Cents::from_units(units), USD))
+            .collect();
+        assert_eq!(shares, expected);
+
+        let total = (money.clone() + Money::from_amount(Cents::from_units(-100), USD)).unwrap();
+        assert_eq!(total.amount(), &Cents::from_units(1_000));
+        assert!((money.clone() + Money::from_amount(Cents::zero(), GBP)).is_err());
+
+        let third: Money<Cents> = money / 3;
+        assert_eq!(third.amount(), &Cents::from_units(366));
+        assert_eq!(
+            third.round(0, RoundingStrategy::MidpointAwayFromZero).amount(),
+            &Cents::from_units(400)
+        );
+        assert_eq!(Money::<Cents>::parse("1.001 USD").unwrap_err(), MoneyError::InvalidAmountFormat);
+    }
+
+    #[test]
+    fn money_fixed_point_keeps_scalar_precision() {
+        type Cents = crate::FixedPoint<2>;
+
+        let money = Money::<Cents>::parse("1000.00 USD").unwrap();
+        assert_eq!(money.clone() * dec!(0.029), Money::parse("29.00 USD").unwrap());
+        assert_eq!(dec!(0.029) * money.clone(), Money::parse("29.00 USD").unwrap());
+        assert_eq!(money.clone() / dec!(0.125), Money::parse("8000.00 USD").unwrap());
+        assert_eq!(dec!(0.125) / Money::<Cents>::parse("0.50 USD").unwrap(), Money::parse("0.25 USD").unwrap());
+
+        let mut scaled = money.clone();
+        scaled *= dec!(0.005);
+        assert_eq!(scaled, Money::parse("5.00 USD").unwrap());
+        scaled /= dec!(0.005);
+        assert_eq!(scaled, money);
+    }
+
+    #[test]
+    fn money_denominations() {
+        use crate::currencies::crypto::{BTC, ETH, USDC};
+
+        let sats = Money::from_denomination(21_000, "sat", BTC).unwrap();
+        assert_eq!(sats, Money::from_minor(21_000, BTC));
+        assert_eq!(sats.to_denomination("mBTC").unwrap(), dec!(0.21));
+        assert_eq!(sats.to_denomination("btc").unwrap(), dec!(0.00021));
+
+        let gas = Money::from_denomination(21_000, "GWEI", ETH).unwrap();
+        assert_eq!(gas.to_denomination("wei").unwrap(), dec!(21_000_000_000_000));
+
+        assert_eq!(
+            Money::from_denomination(1, "gwei", BTC).unwrap_err(),
+            MoneyError::InvalidDenomination
+        );
+        assert_eq!(
+            Money::from_major(1, USDC).to_denomination("wei").unwrap_err(),
+            MoneyError::InvalidDenomination
+        );
+    }
+
+    #[test]
+    fn money_from_raw_str() {
+        use crate::currencies::crypto::{ETH, USDC};
+        use crate::Decimal256;
+
+        let usdc = Money::<Decimal>::from_raw_str("1234567", 6, USDC).unwrap();
+        assert_eq!(usdc, Money::from_minor(1_234_567, USDC));
+        let dust = Money::<Decimal>::from_raw_str("5", 6, USDC).unwrap();
+        assert_eq!(dust.amount(), &dec!(0.000005));
+        let whole = Money::<Decimal>::from_raw_str("42", 0, USDC).unwrap();
+        assert_eq!(whole.amount(), &dec!(42));
+
+        // 2^256 - 1 wei is only representable with Decimal256
+        let max = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+        let eth = Money::<Decimal256>::from_raw_str(max, 18, ETH).unwrap();
+        assert_eq!(eth.amount().to_raw(18).unwrap().to_string(), max);
+
+        assert_eq!(
+            Money::<Decimal>::from_raw_str("-1", 6, USDC).unwrap_err(),
+            MoneyError::InvalidAmountFormat
+        );
+        assert_eq!(
+            Money::<Decimal>::from_raw_str("1.5", 6, USDC).unwrap_err(),
+            MoneyError::InvalidAmountFormat
+        );
+        assert_eq!(
+            Money::<Decimal>::from_raw_str("", 6, USDC).unwrap_err(),
+            MoneyError::InvalidAmountFormat
+        );
+    }
+
+    #[test]
+    fn money_to_minor() {
+        assert_eq!(Money::from_minor(-1050, USD).to_minor_i64(), Ok(-1050));
+        assert_eq!(Money::from_decimal(dec!(10.5000), USD).to_minor_i64(), Ok(1050));
+        assert_eq!(Money::from_minor(1050, BHD).to_minor_i128(), Ok(1050));
+        assert_eq!(
+            Money::from_decimal(dec!(0.001), USD).to_minor_i128(),
+            Err(MoneyError::PrecisionLoss)
+        );
+        assert_eq!(Money::from_major(i64::MAX, USD).to_minor_i64(), Err(MoneyError::Overflow));
+        assert_eq!(
+            Money::from_major(i64::MAX, USD).to_minor_i128(),
+            Ok(i128::from(i64::MAX) * 100)
+        );
+        assert_eq!(Money::from_decimal(Decimal::MAX, USD).to_minor_i128(), Err(MoneyError::Overflow));
+
+        let money = Money::from_decimal(dec!(-10.505), USD);
+        assert_eq!(money.to_minor_i64_rounded(RoundingStrategy::MidpointNearestEven), Ok(-1050));
+        assert_eq!(money.to_minor_i64_rounded(RoundingStrategy::MidpointAwayFromZero), Ok(-1051));
+        assert_eq!(money.to_minor_i128_rounded(RoundingStrategy::ToZero), Ok(-1050));
+    }
+
+    #[test]
+    fn money_to_minor_other_backends() {
+        use crate::currencies::crypto::ETH;
+        use crate::Decimal256;
+
+        let wei = "170141183460469231731687303715884105727"; // i128::MAX
+        let eth = Money::<Decimal256>::from_raw_str(wei, 18, ETH).unwrap();
+        assert_eq!(eth.to_minor_i128(), Ok(i128::MAX));
+        assert_eq!(eth.to_minor_i64(), Err(MoneyError::Overflow));
+        assert_eq!((-eth.clone()).to_minor_i128(), Ok(-i128::MAX));
+        let more = (eth + Money::from_amount(Decimal256::from_minor(1, 18), ETH)).unwrap();
+        assert_eq!(more.to_minor_i128(), Err(MoneyError::Overflow));
+        assert_eq!((-more).to_minor_i128(), Ok(i128::MIN));
+
+        let cents = Money::from_amount(crate::FixedPoint::<4>::from_units(123_456), USD);
+        assert_eq!(cents.to_minor_i64(), Err(MoneyError::PrecisionLoss));
+        assert_eq!(cents.to_minor_i64_rounded(RoundingStrategy::MidpointNearestEven), Ok(1235));
+    }
+
+    #[test]
+    fn money_try_from_f64() {
+        assert_eq!(
+            Money::try_from_f64(-12.5, USD, FloatPolicy::Exact),
+            Ok(Money::from_minor(-1250, USD))
+        );
+        assert_eq!(
+            Money::try_from_f64(1e20, USD, FloatPolicy::Exact),
+            Ok(Money::from_decimal(dec!(100_000_000_000_000_000_000), USD))
+        );
+        assert_eq!(
+            Money::try_from_f64(0.123, USD, FloatPolicy::Exact),
+            Err(MoneyError::PrecisionLoss)
+        );
+        assert_eq!(
+            Money::try_from_f64(0.123, BHD, FloatPolicy::Exact),
+            Ok(Money::from_minor(123, BHD))
+        );
+        assert_eq!(
+            Money::try_from_f64(2.675, USD, FloatPolicy::Round(RoundingStrategy::MidpointAwayFromZero)),
+            Ok(Money::from_minor(268, USD))
+        );
+        assert_eq!(
+            Money::try_from_f64(f64::NAN, USD, FloatPolicy::Exact),
+            Err(MoneyError::NonFiniteAmount)
+        );
+        assert_eq!(
+            Money::try_from_f64(f64::NEG_INFINITY, USD, FloatPolicy::Exact),
+            Err(MoneyError::NonFiniteAmount)
+        );
+        assert_eq!(
+            Money::try_from_f64(1e30, USD, FloatPolicy::Exact),
+            Err(MoneyError::Overflow)
+        );
+    }
+
+    #[test]
+    fn money_to_f64() {
+        assert_eq!(Money::from_minor(-1250, USD).to_f64(), Ok(-12.5));
+        assert_eq!(Money::from_decimal(dec!(0.10), USD).to_f64(), Ok(0.1));
+        let money = Money::from_decimal(dec!(1234567890.1234567890), USD);
+        assert_eq!(money.to_f64(), Err(MoneyError::PrecisionLoss));
+        assert_eq!(money.to_f64_lossy(), 1234567890.1234567);
+
+        let cents = Money::from_amount(crate::FixedPoint::<2>::from_units(1999), USD);
+        assert_eq!(cents.to_f64(), Ok(19.99));
+    }
+
     #[test]
     fn money_sum_iter_empty() {
         let monies: Vec<Money> = vec![];
@@ -0,0 +1,399 @@
+use crate::{Amount, Currency, Exchange, Money, MoneyError, RoundingPolicy};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::btree_map::{self, BTreeMap};
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+
+/// Holds one balance per currency, such as a wallet or a cart with items priced in several currencies.
+///
+/// Unlike `Money`, any currency can be added to or subtracted from a `MoneyBag`. Balances that reach zero are removed,
+/// so two bags holding the same amounts are always equal. Iteration is ordered by currency code.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_money::{Money, MoneyBag};
+/// use rusty_money::currencies::iso::{EUR, USD};
+///
+/// let mut wallet = MoneyBag::new();
+/// wallet += Money::from_major(10, USD);
+/// wallet += Money::from_major(5, EUR);
+/// wallet -= Money::from_major(3, USD);
+///
+/// assert_eq!(wallet.get(&USD), Some(&Money::from_major(7, USD)));
+/// assert_eq!(wallet.len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoneyBag<A = Decimal> {
+    balances: BTreeMap<&'static str, Money<A>>,
+}
+
+impl<A: Amount> MoneyBag<A> {
+    /// Creates an empty bag.
+    pub fn new() -> MoneyBag<A> {
+        MoneyBag {
+            balances: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a Money to the balance of its currency.
+    pub fn add_money(&mut self, money: Money<A>) {
+        let code = money.currency().code;
+        let updated = match self.balances.remove(code) {
+            Some(balance) => Money::from_amount(balance.amount().clone() + money.amount().clone(), *money.currency()),
+            None => money,
+        };
+        if !updated.is_zero() {
+            self.balances.insert(code, updated);
+        }
+    }
+
+    /// Subtracts a Money from the balance of its currency, which may leave a negative balance.
+    pub fn sub_money(&mut self, money: Money<A>) {
+        self.add_money(-money);
+    }
+
+    /// Returns the balance held in a currency, or None if the bag holds nothing in it.
+    pub fn get(&self, currency: &Currency) -> Option<&Money<A>> {
+        self.balances.get(currency.code)
+    }
+
+    /// Returns the balance held in a currency, which is zero if the bag holds nothing in it.
+    pub fn balance(&self, currency: &Currency) -> Money<A> {
+        self.get(currency)
+            .cloned()
+            .unwrap_or_else(|| Money::from_amount(A::zero(), *currency))
+    }
+
+    /// Returns the currencies with a non-zero balance.
+    pub fn currencies(&self) -> impl Iterator<Item = &Currency> {
+        self.balances.values().map(|money| money.currency())
+    }
+
+    /// Returns an iterator over the balances, ordered by currency code.
+    pub fn iter(&self) -> MoneyBagIter<'_, A> {
+        MoneyBagIter {
+            inner: self.balances.values(),
+        }
+    }
+
+    /// Returns the number of currencies with a non-zero balance.
+    pub fn len(&self) -> usize {
+        self.balances.len()
+    }
+
+    /// Returns true if the bag holds no balances.
+    pub fn is_empty(&self) -> bool {
+        self.balances.is_empty()
+    }
+
+    /// Collapses the bag into a single currency, converting every other balance with the rates in an `Exchange`.
+    ///
+    /// Converted balances are summed at full precision and the `RoundingPolicy` is applied once to the total.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::{Exchange, ExchangeRate, Money, MoneyBag, RoundingPolicy};
+    /// use rusty_money::currencies::iso::{EUR, USD};
+    /// use rust_decimal::RoundingStrategy;
+    /// use rust_decimal_macros::dec;
+    ///
+    /// let mut exchange = Exchange::new();
+    /// exchange.set_rate(&ExchangeRate::new(EUR, USD, dec!(1.0853)).unwrap());
+    ///
+    /// let wallet: MoneyBag = vec![Money::from_major(10, USD), Money::from_major(5, EUR)].into_iter().collect();
+    /// let policy = RoundingPolicy::ToCurrency(RoundingStrategy::MidpointNearestEven);
+    /// assert_eq!(wallet.collapse(&exchange, USD, policy).unwrap(), Money::from_minor(1_543, USD));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::MissingExchangeRate` if the exchange has no rate from a held currency to the target, and
+    /// the error of its provider if the provider had to be asked and failed.
+    pub fn collapse(
+        &self,
+        exchange: &Exchange,
+        target: Currency,
+        rounding: RoundingPolicy,
+    ) -> Result<Money<A>, MoneyError> {
+        let mut total = A::zero();
+        for money in self.iter() {
+            if *money.currency() == target {
+                total = total + money.amount().clone();
+                continue;
+            }
+            let lookup = exchange.try_lookup(money.currency(), &target)?;
+            total = total + lookup.rate.convert(money)?.amount().clone();
+        }
+        Ok(Money::from_amount(total, target).round_with(rounding))
+    }
+}
+
+impl<A: Amount> Default for MoneyBag<A> {
+    fn default() -> Self {
+        MoneyBag::new()
+    }
+}
+
+impl<A: Amount> From<Money<A>> for MoneyBag<A> {
+    fn from(money: Money<A>) -> Self {
+        let mut bag = MoneyBag::new();
+        bag.add_money(money);
+        bag
+    }
+}
+
+impl<A: Amount> FromIterator<Money<A>> for MoneyBag<A> {
+    fn from_iter<I: IntoIterator<Item = Money<A>>>(iter: I) -> Self {
+        let mut bag = MoneyBag::new();
+        bag.extend(iter);
+        bag
+    }
+}
+
+impl<A: Amount> Extend<Money<A>> for MoneyBag<A> {
+    fn extend<I: IntoIterator<Item = Money<A>>>(&mut self, iter: I) {
+        for money in iter {
+            self.add_money(money);
+        }
+    }
+}
+
+impl<A: Amount> AddAssign<Money<A>> for MoneyBag<A> {
+    fn add_assign(&mut self, money: Money<A>) {
+        self.add_money(money);
+    }
+}
+
+impl<A: Amount> SubAssign<Money<A>> for MoneyBag<A> {
+    fn sub_assign(&mut self, money: Money<A>) {
+        self.sub_money(money);
+    }
+}
+
+impl<A: Amount> AddAssign for MoneyBag<A> {
+    fn add_assign(&mut self, other: MoneyBag<A>) {
+        self.extend(other);
+    }
+}
+
+impl<A: Amount> SubAssign for MoneyBag<A> {
+    fn sub_assign(&mut self, other: MoneyBag<A>) {
+        self.extend(-other);
+    }
+}
+
+// Unlike `Money`, adding to a bag can never fail, so these operators return the bag directly.
+impl<A: Amount> Add<Money<A>> for MoneyBag<A> {
+    type Output = MoneyBag<A>;
+    fn add(mut self, money: Money<A>) -> MoneyBag<A> {
+        self += money;
+        self
+    }
+}
+
+impl<A: Amount> Sub<Money<A>> for MoneyBag<A> {
+    type Output = MoneyBag<A>;
+    fn sub(mut self, money: Money<A>) -> MoneyBag<A> {
+        self -= money;
+        self
+    }
+}
+
+impl<A: Amount> Add for MoneyBag<A> {
+    type Output = MoneyBag<A>;
+    fn add(mut self, other: MoneyBag<A>) -> MoneyBag<A> {
+        self += other;
+        self
+    }
+}
+
+impl<A: Amount> Sub for MoneyBag<A> {
+    type Output = MoneyBag<A>;
+    fn sub(mut self, other: MoneyBag<A>) -> MoneyBag<A> {
+        self -= other;
+        self
+    }
+}
+
+impl<A: Amount> Neg for MoneyBag<A> {
+    type Output = MoneyBag<A>;
+    fn neg(self) -> MoneyBag<A> {
+        MoneyBag {
+            balances: self.balances.into_iter().map(|(code, money)| (code, -money)).collect(),
+        }
+    }
+}
+
+/// Iterator over the balances of a `MoneyBag`, created by `MoneyBag::iter`.
+#[derive(Debug, Clone)]
+pub struct MoneyBagIter<'a, A> {
+    inner: btree_map::Values<'a, &'static str, Money<A>>,
+}
+
+impl<'a, A> Iterator for MoneyBagIter<'a, A> {
+    type Item = &'a Money<A>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+impl<'a, A: Amount> IntoIterator for &'a MoneyBag<A> {
+    type Item = &'a Money<A>;
+    type IntoIter = MoneyBagIter<'a, A>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<A: Amount> IntoIterator for MoneyBag<A> {
+    type Item = Money<A>;
+    type IntoIter = btree_map::IntoValues<&'static str, Money<A>>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.balances.into_values()
+    }
+}
+
+// Serialized as a list of Money, so the bag follows whichever Money format (tuple or struct) is enabled.
+impl<A: Amount> Serialize for MoneyBag<A>
+where
+    Money<A>: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de, A: Amount> Deserialize<'de> for MoneyBag<A>
+where
+    Money<A>: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // Repeated currencies are merged rather than rejected, matching `FromIterator`.
+        let balances = Vec::<Money<A>>::deserialize(deserializer)?;
+        Ok(balances.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::currencies::iso::{EUR, GBP, JPY, USD};
+    use crate::{ExchangeRate, FileRates, FixedPoint};
+    use rust_decimal::RoundingStrategy;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn money_bag_add_and_sub() {
+        let mut bag = MoneyBag::new();
+        bag += Money::from_minor(1_000, USD);
+        bag += Money::from_minor(500, EUR);
+        bag += Money::from_minor(250, USD);
+        assert_eq!(bag.balance(&USD), Money::from_minor(1_250, USD));
+        assert_eq!(bag.balance(&GBP), Money::from_minor(0, GBP));
+        assert_eq!(bag.get(&GBP), None);
+
+        // Balances can go negative, and drop out of the bag at zero
+        bag -= Money::from_minor(700, EUR);
+        assert_eq!(bag.balance(&EUR), Money::from_minor(-200, EUR));
+        bag -= Money::from_minor(1_250, USD);
+        assert_eq!(bag.get(&USD), None);
+        assert_eq!(bag.len(), 1);
+
+        let other = MoneyBag::from(Money::from_minor(200, EUR));
+        assert!((bag + other).is_empty());
+    }
+
+    #[test]
+    fn money_bag_iterates_in_code_order() {
+        let bag: MoneyBag = vec![
+            Money::from_minor(1, USD),
+            Money::from_minor(2, GBP),
+            Money::from_minor(3, EUR),
+        ]
+        .into_iter()
+        .collect();
+        let codes: Vec<&str> = bag.currencies().map(|c| c.code).collect();
+        assert_eq!(codes, vec!["EUR", "GBP", "USD"]);
+        assert_eq!(bag.iter().count(), 3);
+        assert_eq!(bag.into_iter().next(), Some(Money::from_minor(3, EUR)));
+    }
+
+    #[test]
+    fn money_bag_negation() {
+        let bag: MoneyBag<FixedPoint<2>> = vec![
+            Money::parse("1.50 USD").unwrap(),
+            Money::parse("-2.00 EUR").unwrap(),
+        ]
+        .into_iter()
+        .collect();
+        let negated = -bag.clone();
+        assert_eq!(negated.balance(&USD), Money::parse("-1.50 USD").unwrap());
+        assert_eq!(negated.balance(&EUR), Money::parse("2.00 EUR").unwrap());
+        assert!((bag - negated.clone() + negated.clone() + negated).is_empty());
+    }
+
+    #[test]
+    fn money_bag_collapse() {
+        let mut exchange = Exchange::new();
+        exchange.set_rate(&ExchangeRate::new(EUR, USD, dec!(1.5)).unwrap());
+        exchange.set_rate(&ExchangeRate::new(GBP, USD, dec!(1.333)).unwrap());
+
+        let bag: MoneyBag = vec![
+            Money::from_minor(100, USD),
+            Money::from_minor(100, EUR),
+            Money::from_minor(100, GBP),
+        ]
+        .into_iter()
+        .collect();
+
+        let exact = bag.collapse(&exchange, USD, RoundingPolicy::Unrounded).unwrap();
+        assert_eq!(exact, Money::from_decimal(dec!(3.833), USD));
+        let rounded = bag
+            .collapse(&exchange, USD, RoundingPolicy::ToCurrency(RoundingStrategy::MidpointNearestEven))
+            .unwrap();
+        assert_eq!(rounded, Money::from_minor(383, USD));
+
+        assert_eq!(
+            bag.collapse(&exchange, JPY, RoundingPolicy::Unrounded),
+            Err(MoneyError::MissingExchangeRate)
+        );
+        assert_eq!(
+            MoneyBag::<Decimal>::new().collapse(&exchange, EUR, RoundingPolicy::Unrounded),
+            Ok(Money::from_minor(0, EUR))
+        );
+
+        // A provider that cannot be reached is reported as such, not as a missing rate
+        let offline = exchange.with_provider(FileRates::new("/nonexistent/rates.csv"));
+        assert!(matches!(
+            bag.collapse(&offline, JPY, RoundingPolicy::Unrounded),
+            Err(MoneyError::RateProviderFailed(_))
+        ));
+        assert_eq!(bag.collapse(&offline, USD, RoundingPolicy::Unrounded), Ok(exact));
+    }
+
+    #[test]
+    fn money_bag_serde() {
+        let bag: MoneyBag = vec![Money::from_minor(1_050, USD), Money::from_minor(300, EUR)]
+            .into_iter()
+            .collect();
+        let serialized = serde_json::to_string(&bag).unwrap();
+        #[cfg(not(feature = "serde_struct"))]
+        assert_eq!(serialized, r#"[["3.00","EUR"],["10.50","USD"]]"#);
+        let deserialized: MoneyBag = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, bag);
+
+        #[cfg(not(feature = "serde_struct"))]
+        {
+            let merged: MoneyBag = serde_json::from_str(r#"[["1","USD"],["2","USD"]]"#).unwrap();
+            assert_eq!(merged, MoneyBag::from(Money::from_major(3, USD)));
+        }
+    }
+}
@@ -0,0 +1,206 @@
+pub use chrono::NaiveDateTime;
+
+use crate::{Amount, Money, MoneyError, NaiveDate};
+use rust_decimal::{Decimal, RoundingStrategy};
+
+/// Billing-cycle fractions are used with this many decimal places, far finer than a second of a month.
+const FRACTION_DP: u32 = 9;
+
+impl<A: Amount> Money<A> {
+    /// Splits this recurring amount over consecutive date ranges in proportion to the number of days in each.
+    ///
+    /// `boundaries` holds the start of the billing period, any dates where it is cut, and its end. The result has
+    /// one piece per range and the pieces always add up to this amount. Each piece is rounded down to the minor unit
+    /// and the cents left over go to the earliest non-empty pieces, as with `allocate`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::{Money, NaiveDate};
+    /// use rusty_money::currencies::iso::USD;
+    ///
+    /// // A $30 plan upgraded on the 11th of a 30 day billing period
+    /// let start = NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+    /// let upgrade = NaiveDate::from_ymd_opt(2024, 6, 11).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2024, 7, 1).unwrap();
+    ///
+    /// let pieces = Money::from_major(30, USD).prorate_days(&[start, upgrade, end]).unwrap();
+    /// assert_eq!(pieces, vec![Money::from_major(10, USD), Money::from_major(20, USD)]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::InvalidDateRange` if the boundaries are not in order, and `MoneyError::InvalidRatio` if
+    /// there are fewer than two boundaries or the period is empty.
+    pub fn prorate_days(&self, boundaries: &[NaiveDate]) -> Result<Vec<Money<A>>, MoneyError> {
+        let lengths = boundaries.windows(2).map(|pair| (pair[1] - pair[0]).num_days());
+        self.prorate_lengths(lengths)
+    }
+
+    /// Splits this recurring amount over consecutive time ranges in proportion to the number of seconds in each.
+    ///
+    /// Works like `prorate_days`, for billing that starts and stops at a point in time rather than on a date.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::{Money, NaiveDate};
+    /// use rusty_money::currencies::iso::USD;
+    ///
+    /// let day = NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+    /// let start = day.and_hms_opt(0, 0, 0).unwrap();
+    /// let cancelled = day.and_hms_opt(8, 0, 0).unwrap();
+    /// let end = day.and_hms_opt(23, 59, 59).unwrap() + chrono::Duration::seconds(1);
+    ///
+    /// let pieces = Money::from_major(10, USD).prorate_seconds(&[start, cancelled, end]).unwrap();
+    /// assert_eq!(pieces, vec![Money::from_minor(334, USD), Money::from_minor(666, USD)]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::InvalidDateRange` if the boundaries are not in order, and `MoneyError::InvalidRatio` if
+    /// there are fewer than two boundaries or the period is empty.
+    pub fn prorate_seconds(&self, boundaries: &[NaiveDateTime]) -> Result<Vec<Money<A>>, MoneyError> {
+        let lengths = boundaries.windows(2).map(|pair| (pair[1] - pair[0]).num_seconds());
+        self.prorate_lengths(lengths)
+    }
+
+    /// Splits this recurring amount in proportion to fractions of the billing cycle.
+    ///
+    /// The fractions are relative weights, so `[1/3, 2/3]` and `[1, 2]` split the same way. They are used with
+    /// nine decimal places. The pieces always add up to this amount, rounded as in `prorate_days`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::Money;
+    /// use rusty_money::currencies::iso::USD;
+    /// use rust_decimal_macros::dec;
+    ///
+    /// let pieces = Money::from_major(100, USD).prorate_fractions(&[dec!(0.25), dec!(0.75)]).unwrap();
+    /// assert_eq!(pieces, vec![Money::from_major(25, USD), Money::from_major(75, USD)]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::InvalidRatio` if `fractions` is empty, any fraction is negative or all are zero.
+    pub fn prorate_fractions(&self, fractions: &[Decimal]) -> Result<Vec<Money<A>>, MoneyError> {
+        let scale = Decimal::from(10_u64.pow(FRACTION_DP));
+        let weights: Vec<A> = fractions
+            .iter()
+            .map(|fraction| {
+                let fraction = fraction.round_dp_with_strategy(FRACTION_DP, RoundingStrategy::MidpointNearestEven);
+                A::from_decimal(fraction * scale)
+            })
+            .collect();
+        self.allocate_weighted(&weights)
+    }
+
+    /// Returns the part of this recurring amount that covers `fraction` of the billing cycle.
+    ///
+    /// The result is the first piece of splitting the amount into `fraction` and the rest, so it matches the
+    /// pieces returned by `prorate_fractions`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::Money;
+    /// use rusty_money::currencies::iso::USD;
+    /// use rust_decimal_macros::dec;
+    ///
+    /// let used = Money::from_major(10, USD).prorate(dec!(1) / dec!(3)).unwrap();
+    /// assert_eq!(used, Money::from_minor(334, USD));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::InvalidRatio` if `fraction` is not between 0 and 1.
+    pub fn prorate(&self, fraction: Decimal) -> Result<Money<A>, MoneyError> {
+        if fraction < Decimal::ZERO || fraction > Decimal::ONE {
+            return Err(MoneyError::InvalidRatio);
+        }
+        let mut pieces = self.prorate_fractions(&[fraction, Decimal::ONE - fraction])?;
+        Ok(pieces.swap_remove(0))
+    }
+
+    fn prorate_lengths(&self, lengths: impl Iterator<Item = i64>) -> Result<Vec<Money<A>>, MoneyError> {
+        let lengths: Vec<i64> = lengths.collect();
+        if lengths.is_empty() {
+            return Err(MoneyError::InvalidRatio);
+        }
+        if lengths.iter().any(|&length| length < 0) {
+            return Err(MoneyError::InvalidDateRange);
+        }
+        let weights: Vec<A> = lengths.into_iter().map(|length| A::from_decimal(Decimal::from(length))).collect();
+        self.allocate_weighted(&weights)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::currencies::iso::{JPY, USD};
+    use crate::{FixedPoint, MoneyIterator};
+    use rust_decimal_macros::dec;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn prorate_days_adds_back() {
+        let monthly = Money::from_minor(999, USD);
+        let pieces = monthly
+            .prorate_days(&[date(2024, 2, 1), date(2024, 2, 8), date(2024, 2, 20), date(2024, 3, 1)])
+            .unwrap();
+        // 7, 12 and 10 days of a 29 day February
+        assert_eq!(pieces, vec![Money::from_minor(242, USD), Money::from_minor(413, USD), Money::from_minor(344, USD)]);
+
+        let credit = Money::from_minor(-999, USD).prorate_days(&[date(2024, 2, 1), date(2024, 2, 8), date(2024, 3, 1)]);
+        assert_eq!(credit.unwrap().iter().try_sum(), Ok(Money::from_minor(-999, USD)));
+
+        // Empty ranges get nothing
+        let pieces = monthly.prorate_days(&[date(2024, 2, 1), date(2024, 2, 1), date(2024, 3, 1)]).unwrap();
+        assert_eq!(pieces, vec![Money::from_minor(0, USD), monthly.clone()]);
+    }
+
+    #[test]
+    fn prorate_seconds_adds_back() {
+        let start = date(2024, 6, 1).and_hms_opt(0, 0, 0).unwrap();
+        let boundaries: Vec<NaiveDateTime> =
+            (0..=7).map(|hours| start + chrono::Duration::seconds(hours * 3_601)).collect();
+        let pieces = Money::from_major(1_000, JPY).prorate_seconds(&boundaries).unwrap();
+        assert_eq!(pieces.len(), 7);
+        assert_eq!(pieces[0], Money::from_major(143, JPY));
+        assert_eq!(pieces[6], Money::from_major(142, JPY));
+        assert_eq!(pieces.iter().try_sum(), Ok(Money::from_major(1_000, JPY)));
+    }
+
+    #[test]
+    fn prorate_fractions_and_fixed_point() {
+        let third = dec!(1) / dec!(3);
+        let pieces = Money::from_major(100, USD).prorate_fractions(&[third, third, third]).unwrap();
+        let expected = vec![Money::from_minor(3334, USD), Money::from_minor(3333, USD), Money::from_minor(3333, USD)];
+        assert_eq!(pieces, expected);
+
+        let fixed: Money<FixedPoint<2>> = Money::parse("100.00 USD").unwrap();
+        let pieces = fixed.prorate_fractions(&[third, dec!(2) * third]).unwrap();
+        assert_eq!(pieces, vec![Money::parse("33.34 USD").unwrap(), Money::parse("66.66 USD").unwrap()]);
+        assert_eq!(fixed.prorate(dec!(0.5)).unwrap(), Money::parse("50.00 USD").unwrap());
+    }
+
+    #[test]
+    fn prorate_errors() {
+        let monthly = Money::from_major(30, USD);
+        assert_eq!(monthly.prorate_days(&[date(2024, 6, 1)]), Err(MoneyError::InvalidRatio));
+        assert_eq!(monthly.prorate_days(&[date(2024, 6, 1), date(2024, 6, 1)]), Err(MoneyError::InvalidRatio));
+        assert_eq!(
+            monthly.prorate_days(&[date(2024, 6, 1), date(2024, 7, 1), date(2024, 6, 15)]),
+            Err(MoneyError::InvalidDateRange)
+        );
+        assert_eq!(monthly.prorate_fractions(&[dec!(-0.5), dec!(1.5)]), Err(MoneyError::InvalidRatio));
+        assert_eq!(monthly.prorate(dec!(1.01)), Err(MoneyError::InvalidRatio));
+        assert_eq!(monthly.prorate(dec!(1)).unwrap(), monthly);
+        assert_eq!(monthly.prorate(dec!(0)).unwrap(), Money::from_major(0, USD));
+    }
+}
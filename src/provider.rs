@@ -0,0 +1,305 @@
+use crate::{find_currency, Currency, ExchangeRate, MoneyError};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A source of exchange rates that an `Exchange` queries when it has no rate for a pair.
+///
+/// Implement this to fetch rates from your own service or database, and use `StaticRates` in tests.
+pub trait RateProvider: Debug + Send + Sync {
+    /// Returns the rate from `from` to `to`, or `None` if the provider does not know it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::RateProviderFailed` if the provider could not be queried.
+    fn rate(&self, from: &Currency, to: &Currency) -> Result<Option<ExchangeRate>, MoneyError>;
+}
+
+/// A provider that answers from a fixed set of rates held in memory.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_money::{Exchange, ExchangeRate, RateSource, StaticRates};
+/// use rusty_money::currencies::iso::{EUR, USD};
+/// use rust_decimal_macros::dec;
+///
+/// let rates = StaticRates::new().with_rate(ExchangeRate::new(EUR, USD, dec!(1.085)).unwrap());
+/// let exchange = Exchange::new().with_provider(rates);
+///
+/// let lookup = exchange.lookup(&EUR, &USD).unwrap();
+/// assert_eq!(lookup.source, RateSource::Provided);
+/// assert_eq!(lookup.rate, ExchangeRate::new(EUR, USD, dec!(1.085)).unwrap());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StaticRates {
+    rates: HashMap<(Currency, Currency), ExchangeRate>,
+}
+
+impl StaticRates {
+    pub fn new() -> StaticRates {
+        StaticRates::default()
+    }
+
+    /// Returns the provider with a rate added or replaced.
+    pub fn with_rate(mut self, rate: ExchangeRate) -> StaticRates {
+        self.insert(rate);
+        self
+    }
+
+    /// Adds or replaces a rate.
+    pub fn insert(&mut self, rate: ExchangeRate) {
+        self.rates.insert((rate.from, rate.to), rate);
+    }
+}
+
+impl RateProvider for StaticRates {
+    fn rate(&self, from: &Currency, to: &Currency) -> Result<Option<ExchangeRate>, MoneyError> {
+        Ok(self.rates.get(&(*from, *to)).cloned())
+    }
+}
+
+/// A provider that reads rates from a file each time it is queried, so that edits to the file are picked up.
+///
+/// The file holds one rate per line as `FROM,TO,RATE`, for example `EUR,USD,1.0850`. Blank lines and lines
+/// starting with `#` are ignored. Wrap the provider in `CachedRates` to avoid reading the file on every miss.
+#[derive(Debug, Clone)]
+pub struct FileRates {
+    path: PathBuf,
+}
+
+impl FileRates {
+    pub fn new(path: impl Into<PathBuf>) -> FileRates {
+        FileRates { path: path.into() }
+    }
+
+    /// Reads and parses every rate in the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::RateProviderFailed` if the file cannot be read or a line is malformed, and
+    /// `MoneyError::UnknownCurrency` if a line holds an unknown currency code.
+    pub fn load(&self) -> Result<Vec<ExchangeRate>, MoneyError> {
+        let contents = std::fs::read_to_string(&self.path)
+            .map_err(|err| MoneyError::RateProviderFailed(format!("{}: {}", self.path.display(), err)))?;
+        let malformed = |number: usize| {
+            MoneyError::RateProviderFailed(format!("{}: malformed rate on line {}", self.path.display(), number + 1))
+        };
+
+        let mut rates = Vec::new();
+        for (number, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let [from, to, rate] = fields[..] else {
+                return Err(malformed(number));
+            };
+            let currency = |code: &str| find_currency(code).ok_or(MoneyError::UnknownCurrency(code.to_string()));
+            let (from, to) = (currency(from)?, currency(to)?);
+            let rate = Decimal::from_str(rate).map_err(|_| malformed(number))?;
+            rates.push(ExchangeRate::new(*from, *to, rate)?);
+        }
+        Ok(rates)
+    }
+}
+
+impl RateProvider for FileRates {
+    fn rate(&self, from: &Currency, to: &Currency) -> Result<Option<ExchangeRate>, MoneyError> {
+        let rates = self.load()?;
+        Ok(rates.into_iter().find(|rate| rate.from == *from && rate.to == *to))
+    }
+}
+
+/// A provider that asks a chain of providers in order and returns the first rate found.
+///
+/// Providers that fail are skipped. If no provider has the rate and one of them failed, the first failure is
+/// returned, so that an outage is not mistaken for an unknown pair.
+#[derive(Debug, Clone, Default)]
+pub struct FallbackRates {
+    providers: Vec<Arc<dyn RateProvider>>,
+}
+
+impl FallbackRates {
+    pub fn new() -> FallbackRates {
+        FallbackRates::default()
+    }
+
+    /// Returns the chain with another provider added at the end.
+    pub fn with(mut self, provider: impl RateProvider + 'static) -> FallbackRates {
+        self.providers.push(Arc::new(provider));
+        self
+    }
+}
+
+impl RateProvider for FallbackRates {
+    fn rate(&self, from: &Currency, to: &Currency) -> Result<Option<ExchangeRate>, MoneyError> {
+        let mut failure = None;
+        for provider in &self.providers {
+            match provider.rate(from, to) {
+                Ok(Some(rate)) => return Ok(Some(rate)),
+                Ok(None) => {}
+                Err(err) => {
+                    failure.get_or_insert(err);
+                }
+            }
+        }
+        failure.map_or(Ok(None), Err)
+    }
+}
+
+/// A provider that remembers the rates returned by another provider for a time to live.
+///
+/// Only rates that were found are cached; unknown pairs and failures are asked again on the next query.
+#[derive(Debug)]
+pub struct CachedRates<P> {
+    provider: P,
+    ttl: Duration,
+    cache: Mutex<HashMap<(Currency, Currency), (Instant, ExchangeRate)>>,
+}
+
+impl<P: RateProvider> CachedRates<P> {
+    pub fn new(provider: P, ttl: Duration) -> CachedRates<P> {
+        CachedRates {
+            provider,
+            ttl,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Forgets every cached rate.
+    pub fn clear(&self) {
+        self.cache.lock().expect("rate cache lock poisoned").clear();
+    }
+}
+
+impl<P: RateProvider> RateProvider for CachedRates<P> {
+    fn rate(&self, from: &Currency, to: &Currency) -> Result<Option<ExchangeRate>, MoneyError> {
+        let key = (*from, *to);
+        if let Some((fetched, rate)) = self.cache.lock().expect("rate cache lock poisoned").get(&key) {
+            if fetched.elapsed() < self.ttl {
+                return Ok(Some(rate.clone()));
+            }
+        }
+        let rate = self.provider.rate(from, to)?;
+        if let Some(rate) = &rate {
+            let mut cache = self.cache.lock().expect("rate cache lock poisoned");
+            cache.insert(key, (Instant::now(), rate.clone()));
+        }
+        Ok(rate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::currencies::iso::{EUR, GBP, JPY, USD};
+    use crate::{Exchange, RatePolicy, RateSource};
+    use rust_decimal_macros::dec;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Counts queries and fails for GBP.
+    #[derive(Debug, Default)]
+    struct Counting {
+        queries: AtomicUsize,
+    }
+
+    impl RateProvider for Arc<Counting> {
+        fn rate(&self, from: &Currency, to: &Currency) -> Result<Option<ExchangeRate>, MoneyError> {
+            self.queries.fetch_add(1, Ordering::SeqCst);
+            if *from == GBP || *to == GBP {
+                return Err(MoneyError::RateProviderFailed("offline".into()));
+            }
+            Ok(Some(ExchangeRate::new(*from, *to, dec!(2))?))
+        }
+    }
+
+    fn usd_eur() -> ExchangeRate {
+        ExchangeRate::new(USD, EUR, dec!(0.92)).unwrap()
+    }
+
+    #[test]
+    fn provider_fallback_chain() {
+        let counting = Arc::new(Counting::default());
+        let chain = FallbackRates::new()
+            .with(StaticRates::new().with_rate(usd_eur()))
+            .with(counting.clone());
+
+        assert_eq!(chain.rate(&USD, &EUR), Ok(Some(usd_eur())));
+        assert_eq!(counting.queries.load(Ordering::SeqCst), 0);
+        assert_eq!(chain.rate(&USD, &JPY), Ok(Some(ExchangeRate::new(USD, JPY, dec!(2)).unwrap())));
+        assert_eq!(chain.rate(&USD, &GBP), Err(MoneyError::RateProviderFailed("offline".into())));
+
+        let gbp_eur = ExchangeRate::new(GBP, EUR, dec!(1.17)).unwrap();
+        let skips_failures = FallbackRates::new().with(counting).with(StaticRates::new().with_rate(gbp_eur.clone()));
+        assert_eq!(skips_failures.rate(&GBP, &EUR), Ok(Some(gbp_eur)));
+        assert_eq!(FallbackRates::new().rate(&GBP, &EUR), Ok(None));
+    }
+
+    #[test]
+    fn provider_cache_ttl() {
+        let counting = Arc::new(Counting::default());
+        let cached = CachedRates::new(counting.clone(), Duration::from_secs(3_600));
+        for _ in 0..3 {
+            assert!(cached.rate(&USD, &JPY).unwrap().is_some());
+        }
+        assert_eq!(counting.queries.load(Ordering::SeqCst), 1);
+        assert!(cached.rate(&USD, &GBP).is_err());
+        assert!(cached.rate(&USD, &GBP).is_err());
+        assert_eq!(counting.queries.load(Ordering::SeqCst), 3);
+        cached.clear();
+        cached.rate(&USD, &JPY).unwrap();
+        assert_eq!(counting.queries.load(Ordering::SeqCst), 4);
+
+        let expiring = CachedRates::new(counting.clone(), Duration::ZERO);
+        expiring.rate(&USD, &JPY).unwrap();
+        expiring.rate(&USD, &JPY).unwrap();
+        assert_eq!(counting.queries.load(Ordering::SeqCst), 6);
+    }
+
+    #[test]
+    fn provider_file_rates() {
+        let path = std::env::temp_dir().join(format!("rusty-money-rates-{}.csv", std::process::id()));
+        std::fs::write(&path, "# from,to,rate\nEUR, USD, 1.0850\n\nEUR,JPY,161.2\n").unwrap();
+        let file = FileRates::new(&path);
+        assert_eq!(file.rate(&EUR, &JPY), Ok(Some(ExchangeRate::new(EUR, JPY, dec!(161.2)).unwrap())));
+        assert_eq!(file.rate(&JPY, &EUR), Ok(None));
+
+        std::fs::write(&path, "EUR,USD,1.09\n").unwrap();
+        assert_eq!(file.rate(&EUR, &USD), Ok(Some(ExchangeRate::new(EUR, USD, dec!(1.09)).unwrap())));
+        std::fs::write(&path, "EUR,XYZ,1.09\n").unwrap();
+        assert_eq!(file.rate(&EUR, &USD), Err(MoneyError::UnknownCurrency("XYZ".to_string())));
+        std::fs::write(&path, "EUR;USD;1.09\n").unwrap();
+        assert!(matches!(file.rate(&EUR, &USD), Err(MoneyError::RateProviderFailed(msg)) if msg.ends_with("line 1")));
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(file.rate(&EUR, &USD), Err(MoneyError::RateProviderFailed(_))));
+    }
+
+    #[test]
+    fn exchange_queries_provider_on_miss() {
+        let counting = Arc::new(Counting::default());
+        let mut exchange = Exchange::new().with_provider(counting.clone());
+        exchange.set_rate(&usd_eur());
+
+        assert_eq!(exchange.lookup(&USD, &EUR).unwrap().source, RateSource::Stored);
+        assert_eq!(exchange.lookup(&EUR, &USD).unwrap().source, RateSource::Inverted);
+        assert_eq!(counting.queries.load(Ordering::SeqCst), 0);
+
+        let provided = exchange.lookup(&USD, &JPY).unwrap();
+        assert_eq!(provided.source, RateSource::Provided);
+        assert!(!provided.is_derived());
+        assert_eq!(exchange.try_lookup(&GBP, &JPY), Err(MoneyError::RateProviderFailed("offline".into())));
+        assert_eq!(exchange.lookup(&GBP, &JPY), None);
+
+        let inverted = Exchange::new().with_provider(StaticRates::new().with_rate(usd_eur()));
+        assert_eq!(inverted.lookup(&EUR, &USD).unwrap().source, RateSource::Inverted);
+        assert_eq!(inverted.lookup_with(&EUR, &USD, &RatePolicy::stored_only()), None);
+        assert_eq!(inverted.try_lookup(&EUR, &GBP), Err(MoneyError::MissingExchangeRate));
+    }
+}
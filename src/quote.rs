@@ -0,0 +1,260 @@
+use crate::{Amount, Currency, ExchangeRate, FeeBreakdown, FeeSchedule, Money, MoneyError, RoundingPolicy};
+use rust_decimal::Decimal;
+
+/// The side of a quote taken by the customer, in terms of the base currency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuoteSide {
+    /// The customer buys the base currency and pays the ask price.
+    Buy,
+    /// The customer sells the base currency and receives the bid price.
+    Sell,
+}
+
+/// A two-sided FX quote, such as EUR/USD 1.0848 / 1.0852, with an optional markup and conversion fees.
+///
+/// Prices are in units of the quote currency per unit of the base currency. The markup is taken from the rate on
+/// top of the spread, as with dynamic currency conversion, and the fees are charged in the currency being
+/// converted before the conversion.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_money::{FeeComponent, FeeSchedule, FxQuote, Money, QuoteSide, RoundingPolicy};
+/// use rusty_money::currencies::iso::{EUR, USD};
+/// use rust_decimal::RoundingStrategy;
+/// use rust_decimal_macros::dec;
+///
+/// let quote = FxQuote::new(EUR, USD, dec!(1.08), dec!(1.10))
+///     .unwrap()
+///     .with_markup(dec!(0.03))
+///     .unwrap()
+///     .with_fees(FeeSchedule::new().with(FeeComponent::Fixed { name: None, amount: Money::from_major(2, EUR) }));
+///
+/// let cents = RoundingPolicy::ToCurrency(RoundingStrategy::MidpointNearestEven);
+/// let conversion = quote.convert(&Money::from_major(102, EUR), cents).unwrap();
+/// assert_eq!(conversion.side, QuoteSide::Sell);
+/// assert_eq!(conversion.fees.total, Money::from_major(2, EUR));
+/// assert_eq!(conversion.spread_cost, Money::from_major(1, USD));
+/// assert_eq!(conversion.markup_cost, Money::from_minor(324, USD));
+/// assert_eq!(conversion.converted, Money::from_minor(10_476, USD));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FxQuote<A = Decimal> {
+    pub base: Currency,
+    pub quote: Currency,
+    bid: Decimal,
+    ask: Decimal,
+    markup: Decimal,
+    fees: FeeSchedule<A>,
+}
+
+impl<A: Amount> FxQuote<A> {
+    /// Creates a quote without markup or fees.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::InvalidCurrency` if `base` and `quote` are the same, and `MoneyError::InvalidQuote` if
+    /// `bid` is not positive or `ask` is below `bid`.
+    pub fn new(base: Currency, quote: Currency, bid: Decimal, ask: Decimal) -> Result<FxQuote<A>, MoneyError> {
+        if base == quote {
+            return Err(MoneyError::InvalidCurrency);
+        }
+        if bid <= Decimal::ZERO || ask < bid {
+            return Err(MoneyError::InvalidQuote);
+        }
+        Ok(FxQuote {
+            base,
+            quote,
+            bid,
+            ask,
+            markup: Decimal::ZERO,
+            fees: FeeSchedule::new(),
+        })
+    }
+
+    /// Returns the quote with a markup, the fraction taken from the rate on top of the spread.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::InvalidQuote` if `markup` is negative or not below 1.
+    pub fn with_markup(mut self, markup: Decimal) -> Result<FxQuote<A>, MoneyError> {
+        if markup < Decimal::ZERO || markup >= Decimal::ONE {
+            return Err(MoneyError::InvalidQuote);
+        }
+        self.markup = markup;
+        Ok(self)
+    }
+
+    /// Returns the quote with fees charged on each conversion, in the currency being converted.
+    pub fn with_fees(mut self, fees: FeeSchedule<A>) -> FxQuote<A> {
+        self.fees = fees;
+        self
+    }
+
+    pub fn bid(&self) -> Decimal {
+        self.bid
+    }
+
+    pub fn ask(&self) -> Decimal {
+        self.ask
+    }
+
+    pub fn markup(&self) -> Decimal {
+        self.markup
+    }
+
+    pub fn fees(&self) -> &FeeSchedule<A> {
+        &self.fees
+    }
+
+    /// Returns the price halfway between bid and ask.
+    pub fn mid(&self) -> Decimal {
+        (self.bid + self.ask) / Decimal::TWO
+    }
+
+    /// Returns the spread relative to the mid price, e.g. `0.002` for 0.2%.
+    pub fn relative_spread(&self) -> Decimal {
+        (self.ask - self.bid) / self.mid()
+    }
+
+    /// Returns the mid price as an `ExchangeRate` from the base to the quote currency.
+    pub fn mid_rate(&self) -> ExchangeRate {
+        ExchangeRate::new(self.base, self.quote, self.mid()).expect("a quote has two different currencies")
+    }
+
+    /// Converts an amount in either currency of the quote into the other.
+    ///
+    /// Amounts in the base currency are sold at the bid and amounts in the quote currency buy the base currency at
+    /// the ask. Fees are deducted first, then the markup is taken from the rate. The converted amount and the
+    /// costs are rounded with `rounding`, and the converted amount plus the costs always equal the value of the
+    /// amount after fees at the mid price.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::InvalidCurrency` if `amount` is in neither currency of the quote, and
+    /// `MoneyError::CurrencyMismatch` if the fees are in a different currency than `amount`.
+    pub fn convert(&self, amount: &Money<A>, rounding: RoundingPolicy) -> Result<QuoteConversion<A>, MoneyError> {
+        let (side, target, side_rate, mid_rate) = if *amount.currency() == self.base {
+            (QuoteSide::Sell, self.quote, self.bid, self.mid())
+        } else if *amount.currency() == self.quote {
+            (QuoteSide::Buy, self.base, Decimal::ONE / self.ask, Decimal::ONE / self.mid())
+        } else {
+            return Err(MoneyError::InvalidCurrency);
+        };
+        let rate = side_rate * (Decimal::ONE - self.markup);
+
+        let fees = self.fees.evaluate(amount, rounding)?;
+        let net = amount.amount().clone() - fees.total.amount().clone();
+        let value = |rate: Decimal| Money::from_amount(net.mul_decimal(rate), target).round_with(rounding);
+        let (at_mid, at_side, converted) = (value(mid_rate), value(side_rate), value(rate));
+
+        Ok(QuoteConversion {
+            side,
+            fees,
+            mid_rate,
+            rate,
+            spread_cost: Money::from_amount(at_mid.amount().clone() - at_side.amount().clone(), target),
+            markup_cost: Money::from_amount(at_side.amount().clone() - converted.amount().clone(), target),
+            converted,
+        })
+    }
+}
+
+/// The result of converting with an `FxQuote`, with the breakdown of costs needed for a customer disclosure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuoteConversion<A = Decimal> {
+    /// The side of the quote taken by the customer.
+    pub side: QuoteSide,
+    /// The fees charged in the currency that was converted.
+    pub fees: FeeBreakdown<A>,
+    /// The mid price, in units of the target currency per unit of the converted currency.
+    pub mid_rate: Decimal,
+    /// The rate applied after spread and markup, in units of the target currency per unit of the converted currency.
+    pub rate: Decimal,
+    /// The cost of the spread, in the target currency.
+    pub spread_cost: Money<A>,
+    /// The cost of the markup, in the target currency.
+    pub markup_cost: Money<A>,
+    /// The amount received, in the target currency.
+    pub converted: Money<A>,
+}
+
+impl<A: Amount> QuoteConversion<A> {
+    /// Returns how much worse the applied rate is than the mid price, e.g. `0.035` for 3.5%.
+    pub fn markup_over_mid(&self) -> Decimal {
+        Decimal::ONE - self.rate / self.mid_rate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::currencies::iso::{EUR, GBP, USD};
+    use crate::{FeeComponent, FixedPoint, MoneyIterator};
+    use rust_decimal::RoundingStrategy;
+    use rust_decimal_macros::dec;
+
+    const CENTS: RoundingPolicy = RoundingPolicy::ToCurrency(RoundingStrategy::MidpointNearestEven);
+
+    #[test]
+    fn quote_picks_side() {
+        let quote: FxQuote = FxQuote::new(EUR, USD, dec!(1.25), dec!(1.28)).unwrap();
+        assert_eq!(quote.mid(), dec!(1.265));
+        assert_eq!(quote.mid_rate(), ExchangeRate::new(EUR, USD, dec!(1.265)).unwrap());
+        assert_eq!(quote.relative_spread().round_dp(6), dec!(0.023715));
+
+        let sell = quote.convert(&Money::from_major(100, EUR), CENTS).unwrap();
+        assert_eq!(sell.side, QuoteSide::Sell);
+        assert_eq!(sell.rate, dec!(1.25));
+        assert_eq!(sell.converted, Money::from_major(125, USD));
+        assert_eq!(sell.spread_cost, Money::from_minor(150, USD));
+        assert_eq!(sell.markup_cost, Money::from_major(0, USD));
+        assert!(sell.fees.items.is_empty());
+
+        let buy = quote.convert(&Money::from_major(128, USD), CENTS).unwrap();
+        assert_eq!(buy.side, QuoteSide::Buy);
+        assert_eq!(buy.converted, Money::from_major(100, EUR));
+        assert_eq!(buy.spread_cost, Money::from_minor(119, EUR));
+        assert_eq!(buy.markup_over_mid().round_dp(6), dec!(0.011719));
+
+        assert_eq!(quote.convert(&Money::from_major(1, GBP), CENTS), Err(MoneyError::InvalidCurrency));
+    }
+
+    #[test]
+    fn quote_markup_and_fees() {
+        let commission = FeeComponent::Percentage { name: Some("commission".into()), rate: dec!(0.01) };
+        let fees = FeeSchedule::new().with(commission);
+        let quote = FxQuote::new(GBP, EUR, dec!(1.1700), dec!(1.1702))
+            .unwrap()
+            .with_markup(dec!(0.035))
+            .unwrap()
+            .with_fees(fees);
+        assert_eq!(quote.markup(), dec!(0.035));
+
+        let amount = Money::from_major(500, GBP);
+        let conversion = quote.convert(&amount, CENTS).unwrap();
+        assert_eq!(conversion.fees.total, Money::from_major(5, GBP));
+        assert_eq!(conversion.converted, Money::from_minor(55_888, EUR));
+        let at_mid = [&conversion.converted, &conversion.spread_cost, &conversion.markup_cost].into_iter().try_sum();
+        assert_eq!(at_mid, Ok(Money::from_minor(57_920, EUR)));
+        assert_eq!(conversion.markup_over_mid().round_dp(4), dec!(0.0351));
+
+        let exact = quote.convert(&amount, RoundingPolicy::Unrounded).unwrap();
+        assert_eq!(exact.converted, Money::from_decimal(dec!(495) * dec!(1.1700) * dec!(0.965), EUR));
+
+        let fixed: FxQuote<FixedPoint<4>> = FxQuote::new(GBP, EUR, dec!(1.1700), dec!(1.1702)).unwrap();
+        let conversion = fixed.convert(&Money::parse("10.00 EUR").unwrap(), CENTS).unwrap();
+        assert_eq!(conversion.converted, Money::parse("8.55 GBP").unwrap());
+        assert_eq!(conversion.spread_cost, Money::parse("0.00 GBP").unwrap());
+    }
+
+    #[test]
+    fn quote_validation() {
+        assert_eq!(FxQuote::<Decimal>::new(EUR, EUR, dec!(1), dec!(1)), Err(MoneyError::InvalidCurrency));
+        assert_eq!(FxQuote::<Decimal>::new(EUR, USD, dec!(0), dec!(1)), Err(MoneyError::InvalidQuote));
+        assert_eq!(FxQuote::<Decimal>::new(EUR, USD, dec!(1.1), dec!(1.0)), Err(MoneyError::InvalidQuote));
+        let quote = FxQuote::<Decimal>::new(EUR, USD, dec!(1.1), dec!(1.1)).unwrap();
+        assert_eq!(quote.clone().with_markup(dec!(-0.01)), Err(MoneyError::InvalidQuote));
+        assert_eq!(quote.with_markup(dec!(1)), Err(MoneyError::InvalidQuote));
+    }
+}
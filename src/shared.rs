@@ -0,0 +1,237 @@
+use crate::{Amount, ConversionReport, Currency, Exchange, Money, MoneyError};
+use arc_swap::ArcSwap;
+use rust_decimal::Decimal;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex};
+
+/// An `Exchange` shared between threads, which readers use through lock-free snapshots.
+///
+/// Readers never wait for a refresh of the rates: `snapshot` returns the current rate set, and it stays the same
+/// for as long as the snapshot is held. Writers build the next rate set on the side and swap it in at once, so a
+/// reader sees either all of an update or none of it. Each rate set has a version, one higher than the last.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_money::{Exchange, ExchangeRate, Money, SharedExchange};
+/// use rusty_money::currencies::iso::{EUR, USD};
+/// use rust_decimal_macros::dec;
+///
+/// let shared = SharedExchange::new(Exchange::new());
+/// let before = shared.snapshot();
+///
+/// let version = shared.update(|exchange| {
+///     exchange.set_rate(&ExchangeRate::new(EUR, USD, dec!(1.08))?);
+///     Ok(())
+/// });
+/// assert_eq!(version, Ok(2));
+///
+/// let conversion = shared.convert(&Money::from_major(100, EUR), &USD).unwrap();
+/// assert_eq!(conversion.version, 2);
+/// assert_eq!(conversion.report.converted, Money::from_major(108, USD));
+///
+/// // Snapshots taken before the update keep the old rate set
+/// assert_eq!(before.version(), 1);
+/// assert!(before.get_rate(&EUR, &USD).is_none());
+/// ```
+#[derive(Debug)]
+pub struct SharedExchange {
+    current: ArcSwap<ExchangeSnapshot>,
+    writer: Mutex<()>,
+}
+
+impl SharedExchange {
+    /// Shares an exchange as the rate set with version 1.
+    pub fn new(exchange: Exchange) -> SharedExchange {
+        SharedExchange {
+            current: ArcSwap::from_pointee(ExchangeSnapshot { version: 1, exchange }),
+            writer: Mutex::new(()),
+        }
+    }
+
+    /// Returns the current rate set, which does not change while it is held.
+    pub fn snapshot(&self) -> Arc<ExchangeSnapshot> {
+        self.current.load_full()
+    }
+
+    /// Returns the version of the current rate set.
+    pub fn version(&self) -> u64 {
+        self.current.load().version
+    }
+
+    /// Replaces the rate set with `exchange` and returns its version.
+    pub fn replace(&self, exchange: Exchange) -> u64 {
+        let _writer = self.writer.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        self.publish(exchange)
+    }
+
+    /// Applies `change` to a copy of the current rate set and swaps the copy in, returning its version.
+    ///
+    /// Updates run one at a time, so none is lost to another made at the same time. Readers keep using the
+    /// current rate set until `change` has finished.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `change`, in which case the rate set is left as it was.
+    pub fn update<F>(&self, change: F) -> Result<u64, MoneyError>
+    where
+        F: FnOnce(&mut Exchange) -> Result<(), MoneyError>,
+    {
+        let _writer = self.writer.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut exchange = self.current.load().exchange.clone();
+        change(&mut exchange)?;
+        Ok(self.publish(exchange))
+    }
+
+    /// Converts an amount into `target` with the current rate set, like `Exchange::convert_with_report`, and
+    /// records the version of the rate set used.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as `Exchange::convert`.
+    pub fn convert<A: Amount>(
+        &self,
+        amount: &Money<A>,
+        target: &Currency,
+    ) -> Result<SharedConversion<A>, MoneyError> {
+        let snapshot = self.current.load();
+        Ok(SharedConversion {
+            version: snapshot.version,
+            report: snapshot.exchange.convert_with_report(amount, target)?,
+        })
+    }
+
+    fn publish(&self, exchange: Exchange) -> u64 {
+        let version = self.current.load().version + 1;
+        self.current.store(Arc::new(ExchangeSnapshot { version, exchange }));
+        version
+    }
+}
+
+impl Default for SharedExchange {
+    fn default() -> Self {
+        SharedExchange::new(Exchange::new())
+    }
+}
+
+impl From<Exchange> for SharedExchange {
+    fn from(exchange: Exchange) -> Self {
+        SharedExchange::new(exchange)
+    }
+}
+
+/// A rate set of a `SharedExchange` with its version. It dereferences to the `Exchange`.
+#[derive(Debug)]
+pub struct ExchangeSnapshot {
+    version: u64,
+    exchange: Exchange,
+}
+
+impl ExchangeSnapshot {
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    pub fn exchange(&self) -> &Exchange {
+        &self.exchange
+    }
+}
+
+impl Deref for ExchangeSnapshot {
+    type Target = Exchange;
+
+    fn deref(&self) -> &Exchange {
+        &self.exchange
+    }
+}
+
+/// The result of a conversion with a `SharedExchange`, with the version of the rate set it used.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SharedConversion<A = Decimal> {
+    /// The version of the rate set used.
+    pub version: u64,
+    /// The converted amount with the rate used.
+    pub report: ConversionReport<A>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::currencies::iso::{EUR, GBP, USD};
+    use crate::ExchangeRate;
+    use rust_decimal_macros::dec;
+    use std::thread;
+
+    fn rates(rate: Decimal) -> Exchange {
+        let mut exchange = Exchange::new();
+        exchange.set_rate(&ExchangeRate::new(EUR, USD, rate).unwrap());
+        exchange.set_rate(&ExchangeRate::new(GBP, USD, rate).unwrap());
+        exchange
+    }
+
+    #[test]
+    fn shared_exchange_versions() {
+        let shared = SharedExchange::from(rates(dec!(1)));
+        let first = shared.snapshot();
+        assert_eq!(shared.replace(rates(dec!(2))), 2);
+        assert_eq!(shared.version(), 2);
+        assert_eq!(first.version(), 1);
+        assert_eq!(first.get_rate(&EUR, &USD), Some(ExchangeRate::new(EUR, USD, dec!(1)).unwrap()));
+
+        let failed = shared.update(|exchange| {
+            exchange.set_rate(&ExchangeRate::new(EUR, USD, dec!(3)).unwrap());
+            Err(MoneyError::MissingExchangeRate)
+        });
+        assert_eq!(failed, Err(MoneyError::MissingExchangeRate));
+        assert_eq!(shared.version(), 2);
+        assert_eq!(shared.snapshot().get_rate(&EUR, &USD), Some(ExchangeRate::new(EUR, USD, dec!(2)).unwrap()));
+
+        let conversion = shared.convert(&Money::from_major(5, GBP), &USD).unwrap();
+        assert_eq!(conversion.version, 2);
+        assert_eq!(conversion.report.converted, Money::from_major(10, USD));
+        assert_eq!(shared.convert(&Money::from_major(5, GBP), &EUR).unwrap().version, 2);
+    }
+
+    #[test]
+    fn shared_exchange_readers_see_whole_updates() {
+        let shared = Arc::new(SharedExchange::from(rates(dec!(1))));
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let shared = Arc::clone(&shared);
+                thread::spawn(move || {
+                    let mut last = 0;
+                    for _ in 0..500 {
+                        let snapshot = shared.snapshot();
+                        assert!(snapshot.version() >= last);
+                        last = snapshot.version();
+                        // Both pairs are set by the same update, whose rate is its version
+                        let rate = Decimal::from(snapshot.version());
+                        assert_eq!(snapshot.get_rate(&EUR, &USD), Some(ExchangeRate::new(EUR, USD, rate).unwrap()));
+                        assert_eq!(snapshot.get_rate(&GBP, &USD), Some(ExchangeRate::new(GBP, USD, rate).unwrap()));
+                    }
+                })
+            })
+            .collect();
+        let writers: Vec<_> = (0..2)
+            .map(|_| {
+                let shared = Arc::clone(&shared);
+                thread::spawn(move || {
+                    for _ in 0..50 {
+                        shared
+                            .update(|exchange| {
+                                let next = Decimal::from(shared.version() + 1);
+                                exchange.set_rate(&ExchangeRate::new(EUR, USD, next)?);
+                                exchange.set_rate(&ExchangeRate::new(GBP, USD, next)?);
+                                Ok(())
+                            })
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in readers.into_iter().chain(writers) {
+            handle.join().unwrap();
+        }
+        assert_eq!(shared.version(), 101);
+    }
+}
@@ -0,0 +1,393 @@
+//! Statistical aggregations over collections of same-currency Money.
+//!
+//! Every function accepts any iterator of `Money` or `&Money`, returns `MoneyError::EmptySum` when it is empty and
+//! `MoneyError::CurrencyMismatch` when the items do not share a single currency. Functions that compute a new amount
+//! (rather than picking an existing one) take a `RoundingPolicy` for the result.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_money::{stats, Money, RoundingPolicy};
+//! use rusty_money::currencies::iso::USD;
+//! use rust_decimal::RoundingStrategy;
+//!
+//! let prices = [Money::from_minor(1_000, USD), Money::from_minor(2_000, USD), Money::from_minor(4_000, USD)];
+//! let policy = RoundingPolicy::ToCurrency(RoundingStrategy::MidpointNearestEven);
+//!
+//! assert_eq!(stats::mean(&prices, policy).unwrap(), Money::from_minor(2_333, USD));
+//! assert_eq!(stats::median(&prices, policy).unwrap(), Money::from_minor(2_000, USD));
+//! assert_eq!(stats::max(&prices).unwrap(), Money::from_minor(4_000, USD));
+//! ```
+use crate::{Amount, Currency, Money, MoneyError, RoundingPolicy};
+use rust_decimal::Decimal;
+use std::borrow::Borrow;
+
+/// Returns the arithmetic mean of the amounts.
+///
+/// # Errors
+///
+/// Returns `EmptySum` for an empty iterator, `CurrencyMismatch` for mixed currencies and `Overflow` if the total of
+/// the amounts cannot be represented.
+pub fn mean<A, I>(iter: I, rounding: RoundingPolicy) -> Result<Money<A>, MoneyError>
+where
+    A: Amount,
+    I: IntoIterator,
+    I::Item: Borrow<Money<A>>,
+{
+    let (currency, amounts) = collect(iter)?;
+    let count = A::from_decimal(Decimal::from(amounts.len()));
+    let total = amounts.iter().try_fold(A::zero(), |acc, amount| acc.checked_add(amount));
+    Ok(Money::from_amount(total.ok_or(MoneyError::Overflow)? / count, currency).round_with(rounding))
+}
+
+/// Returns the median of the amounts, averaging the two middle values for an even number of items.
+///
+/// # Errors
+///
+/// Returns `EmptySum` for an empty iterator and `CurrencyMismatch` for mixed currencies.
+pub fn median<A, I>(iter: I, rounding: RoundingPolicy) -> Result<Money<A>, MoneyError>
+where
+    A: Amount,
+    I: IntoIterator,
+    I::Item: Borrow<Money<A>>,
+{
+    percentile(iter, Decimal::from(50), rounding)
+}
+
+/// Returns the `p`th percentile (0 to 100) of the amounts.
+///
+/// Uses linear interpolation between the closest ranks, the same method as the default of numpy and most
+/// spreadsheets, so `percentile(.., 50, ..)` is the median.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_money::{stats, Money, RoundingPolicy};
+/// use rusty_money::currencies::iso::USD;
+/// use rust_decimal_macros::dec;
+///
+/// let latencies: Vec<_> = (1..=5).map(|n| Money::from_major(n * 10, USD)).collect();
+/// let p90 = stats::percentile(&latencies, dec!(90), RoundingPolicy::Unrounded).unwrap();
+/// assert_eq!(p90, Money::from_major(46, USD));
+/// ```
+///
+/// # Errors
+///
+/// Returns `InvalidPercentile` when `p` is outside of 0 to 100, `EmptySum` for an empty iterator and
+/// `CurrencyMismatch` for mixed currencies.
+pub fn percentile<A, I>(iter: I, p: Decimal, rounding: RoundingPolicy) -> Result<Money<A>, MoneyError>
+where
+    A: Amount,
+    I: IntoIterator,
+    I::Item: Borrow<Money<A>>,
+{
+    if p < Decimal::ZERO || p > Decimal::ONE_HUNDRED {
+        return Err(MoneyError::InvalidPercentile);
+    }
+    let (currency, mut amounts) = collect(iter)?;
+    amounts.sort();
+
+    let rank = p / Decimal::ONE_HUNDRED * Decimal::from(amounts.len() - 1);
+    let lower = rank.floor();
+    let fraction = rank - lower;
+    // `rank` lies between 0 and len - 1, so the conversion cannot fail.
+    let index = usize::try_from(lower).expect("percentile rank fits into usize");
+
+    let mut value = amounts[index].clone();
+    if !fraction.is_zero() {
+        let step = amounts[index + 1].clone() - amounts[index].clone();
+        value = value + step.mul_decimal(fraction);
+    }
+    Ok(Money::from_amount(value, currency).round_with(rounding))
+}
+
+/// Returns the weighted mean of `(amount, weight)` pairs, such as an average cost or a VWAP weighted by volume.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_money::{stats, Money, RoundingPolicy};
+/// use rusty_money::currencies::iso::USD;
+/// use rust_decimal::RoundingStrategy;
+/// use rust_decimal_macros::dec;
+///
+/// // Bought 100 shares at $10.00 and 300 shares at $12.00
+/// let fills = [(Money::from_minor(1_000, USD), dec!(100)), (Money::from_minor(1_200, USD), dec!(300))];
+/// let policy = RoundingPolicy::ToCurrency(RoundingStrategy::MidpointNearestEven);
+/// assert_eq!(stats::weighted_mean(fills, policy).unwrap(), Money::from_minor(1_150, USD));
+/// ```
+///
+/// # Errors
+///
+/// Returns `InvalidRatio` when a weight is negative or all weights are zero, `EmptySum` for an empty iterator,
+/// `CurrencyMismatch` for mixed currencies and `Overflow` if the weighted total or the total weight cannot be
+/// represented.
+pub fn weighted_mean<A, I, M>(iter: I, rounding: RoundingPolicy) -> Result<Money<A>, MoneyError>
+where
+    A: Amount,
+    I: IntoIterator<Item = (M, Decimal)>,
+    M: Borrow<Money<A>>,
+{
+    let mut currency: Option<Currency> = None;
+    let mut weighted_total = A::zero();
+    let mut total_weight = Decimal::ZERO;
+
+    for (money, weight) in iter {
+        let money = money.borrow();
+        check_currency(&mut currency, money)?;
+        if weight.is_sign_negative() && !weight.is_zero() {
+            return Err(MoneyError::InvalidRatio);
+        }
+        weighted_total = weighted_total
+            .checked_add(&money.amount().mul_decimal(weight))
+            .ok_or(MoneyError::Overflow)?;
+        total_weight = total_weight.checked_add(weight).ok_or(MoneyError::Overflow)?;
+    }
+
+    let currency = currency.ok_or(MoneyError::EmptySum)?;
+    if total_weight.is_zero() {
+        return Err(MoneyError::InvalidRatio);
+    }
+    let mean = weighted_total.div_decimal(total_weight);
+    Ok(Money::from_amount(mean, currency).round_with(rounding))
+}
+
+/// Returns the smallest amount.
+///
+/// # Errors
+///
+/// Returns `EmptySum` for an empty iterator and `CurrencyMismatch` for mixed currencies.
+pub fn min<A, I>(iter: I) -> Result<Money<A>, MoneyError>
+where
+    A: Amount,
+    I: IntoIterator,
+    I::Item: Borrow<Money<A>>,
+{
+    let (currency, amounts) = collect(iter)?;
+    let smallest = amounts.into_iter().min().expect("collect rejects empty input");
+    Ok(Money::from_amount(smallest, currency))
+}
+
+/// Returns the largest amount.
+///
+/// # Errors
+///
+/// Returns `EmptySum` for an empty iterator and `CurrencyMismatch` for mixed currencies.
+pub fn max<A, I>(iter: I) -> Result<Money<A>, MoneyError>
+where
+    A: Amount,
+    I: IntoIterator,
+    I::Item: Borrow<Money<A>>,
+{
+    let (currency, amounts) = collect(iter)?;
+    let largest = amounts.into_iter().max().expect("collect rejects empty input");
+    Ok(Money::from_amount(largest, currency))
+}
+
+/// Returns the population standard deviation of the amounts.
+///
+/// # Errors
+///
+/// Returns `EmptySum` for an empty iterator, `CurrencyMismatch` for mixed currencies and `Overflow` when the
+/// squared deviations do not fit the amount backend.
+pub fn std_dev<A, I>(iter: I, rounding: RoundingPolicy) -> Result<Money<A>, MoneyError>
+where
+    A: Amount,
+    I: IntoIterator,
+    I::Item: Borrow<Money<A>>,
+{
+    let (currency, amounts) = collect(iter)?;
+    let count = amounts.len();
+    Ok(Money::from_amount(deviation(amounts, count)?, currency).round_with(rounding))
+}
+
+/// Returns the sample standard deviation of the amounts, which divides by `n - 1` instead of `n`.
+///
+/// # Errors
+///
+/// Returns `EmptySum` when there are fewer than two items, `CurrencyMismatch` for mixed currencies and `Overflow`
+/// when the squared deviations do not fit the amount backend.
+pub fn sample_std_dev<A, I>(iter: I, rounding: RoundingPolicy) -> Result<Money<A>, MoneyError>
+where
+    A: Amount,
+    I: IntoIterator,
+    I::Item: Borrow<Money<A>>,
+{
+    let (currency, amounts) = collect(iter)?;
+    if amounts.len() < 2 {
+        return Err(MoneyError::EmptySum);
+    }
+    let count = amounts.len() - 1;
+    Ok(Money::from_amount(deviation(amounts, count)?, currency).round_with(rounding))
+}
+
+/// Collects the amounts of an iterator, checking that it is non-empty and holds a single currency.
+fn collect<A, I>(iter: I) -> Result<(Currency, Vec<A>), MoneyError>
+where
+    A: Amount,
+    I: IntoIterator,
+    I::Item: Borrow<Money<A>>,
+{
+    let mut currency: Option<Currency> = None;
+    let mut amounts = Vec::new();
+    for money in iter {
+        let money = money.borrow();
+        check_currency(&mut currency, money)?;
+        amounts.push(money.amount().clone());
+    }
+    let currency = currency.ok_or(MoneyError::EmptySum)?;
+    Ok((currency, amounts))
+}
+
+fn check_currency<A: Amount>(currency: &mut Option<Currency>, money: &Money<A>) -> Result<(), MoneyError> {
+    match currency {
+        None => *currency = Some(*money.currency()),
+        Some(expected) if *expected != *money.currency() => return Err(MoneyError::CurrencyMismatch),
+        Some(_) => {}
+    }
+    Ok(())
+}
+
+/// Square root of the sum of squared deviations from the mean divided by `divisor`.
+fn deviation<A: Amount>(amounts: Vec<A>, divisor: usize) -> Result<A, MoneyError> {
+    let total = amounts.iter().try_fold(A::zero(), |acc, amount| acc.checked_add(amount));
+    let mean = total.ok_or(MoneyError::Overflow)?.div_decimal(Decimal::from(amounts.len()));
+    let squares = amounts.into_iter().try_fold(A::zero(), |acc, amount| {
+        let delta = amount.checked_add(&-mean.clone())?;
+        acc.checked_add(&delta.checked_mul(&delta)?)
+    });
+    Ok(sqrt(squares.ok_or(MoneyError::Overflow)?.div_decimal(Decimal::from(divisor))))
+}
+
+/// Newton's method square root, written against `Amount` so it works for every backend.
+///
+/// Starting at or above the root, each iteration strictly decreases until the backend's precision is exhausted,
+/// so iteration stops as soon as the estimate no longer shrinks.
+fn sqrt<A: Amount>(value: A) -> A {
+    if value.is_zero() {
+        return value;
+    }
+    let two = A::one() + A::one();
+    let mut estimate = if value > A::one() { value.clone() } else { A::one() };
+    loop {
+        let next = (estimate.clone() + value.clone() / estimate.clone()) / two.clone();
+        if next >= estimate {
+            return estimate;
+        }
+        estimate = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::currencies::iso::{EUR, USD};
+    use crate::FixedPoint;
+    use rust_decimal::RoundingStrategy;
+    use rust_decimal_macros::dec;
+
+    const CENTS: RoundingPolicy = RoundingPolicy::ToCurrency(RoundingStrategy::MidpointNearestEven);
+
+    fn usd(amounts: &[i64]) -> Vec<Money> {
+        amounts.iter().map(|minor| Money::from_minor(*minor, USD)).collect()
+    }
+
+    #[test]
+    fn stats_mean_and_median() {
+        let values = usd(&[100, 200, 400, 700]);
+        assert_eq!(mean(&values, CENTS).unwrap(), Money::from_minor(350, USD));
+        assert_eq!(median(&values, CENTS).unwrap(), Money::from_minor(300, USD));
+        assert_eq!(median(usd(&[300, 100, 200]), CENTS).unwrap(), Money::from_minor(200, USD));
+
+        // Rounding is applied only when asked for
+        let thirds = usd(&[100, 100, 101]);
+        assert_eq!(mean(&thirds, CENTS).unwrap(), Money::from_minor(100, USD));
+        assert!(mean(&thirds, RoundingPolicy::Unrounded).unwrap() > Money::from_minor(100, USD));
+    }
+
+    #[test]
+    fn stats_percentiles() {
+        let values = usd(&[1_000, 2_000, 3_000, 4_000, 5_000]);
+        assert_eq!(percentile(&values, dec!(0), CENTS).unwrap(), Money::from_minor(1_000, USD));
+        assert_eq!(percentile(&values, dec!(100), CENTS).unwrap(), Money::from_minor(5_000, USD));
+        assert_eq!(percentile(&values, dec!(25), CENTS).unwrap(), Money::from_minor(2_000, USD));
+        assert_eq!(percentile(&values, dec!(95), CENTS).unwrap(), Money::from_minor(4_800, USD));
+        assert_eq!(percentile(&values, dec!(101), CENTS), Err(MoneyError::InvalidPercentile));
+        assert_eq!(percentile(&values, dec!(-1), CENTS), Err(MoneyError::InvalidPercentile));
+
+        // The fraction between ranks is not rounded to the precision of the backend
+        let fixed: Vec<Money<FixedPoint<2>>> =
+            vec![Money::parse("10.00 USD").unwrap(), Money::parse("20.00 USD").unwrap()];
+        assert_eq!(percentile(&fixed, dec!(37.5), CENTS).unwrap(), Money::parse("13.75 USD").unwrap());
+    }
+
+    #[test]
+    fn stats_weighted_mean() {
+        let fills = vec![(Money::from_minor(1_000, USD), dec!(1)), (Money::from_minor(1_100, USD), dec!(2))];
+        assert_eq!(weighted_mean(fills, CENTS).unwrap(), Money::from_minor(1_067, USD));
+
+        let zero_weights = vec![(Money::from_minor(1_000, USD), dec!(0))];
+        assert_eq!(weighted_mean(zero_weights, CENTS), Err(MoneyError::InvalidRatio));
+        let negative = vec![(Money::from_minor(1_000, USD), dec!(-1))];
+        assert_eq!(weighted_mean(negative, CENTS), Err(MoneyError::InvalidRatio));
+
+        // Weights are not rounded to the precision of the backend
+        let fixed: Vec<(Money<FixedPoint<2>>, Decimal)> = vec![
+            (Money::parse("10.00 USD").unwrap(), dec!(0.333)),
+            (Money::parse("20.00 USD").unwrap(), dec!(0.667)),
+        ];
+        assert_eq!(weighted_mean(fixed, CENTS).unwrap(), Money::parse("16.67 USD").unwrap());
+    }
+
+    #[test]
+    fn stats_min_max() {
+        let values = usd(&[500, -200, 900]);
+        assert_eq!(min(&values).unwrap(), Money::from_minor(-200, USD));
+        assert_eq!(max(values).unwrap(), Money::from_minor(900, USD));
+    }
+
+    #[test]
+    fn stats_std_dev() {
+        let values = usd(&[200, 400, 400, 400, 500, 500, 700, 900]);
+        assert_eq!(std_dev(&values, CENTS).unwrap(), Money::from_minor(200, USD));
+        assert_eq!(sample_std_dev(&values, CENTS).unwrap(), Money::from_minor(214, USD));
+        assert_eq!(std_dev(usd(&[700]), CENTS).unwrap(), Money::from_minor(0, USD));
+        assert_eq!(sample_std_dev(usd(&[700]), CENTS), Err(MoneyError::EmptySum));
+
+        let fixed: Vec<Money<FixedPoint<4>>> = ["2", "4", "4", "4", "5", "5", "7", "9"]
+            .iter()
+            .map(|amount| Money::parse(&format!("{} USD", amount)).unwrap())
+            .collect();
+        assert_eq!(std_dev(&fixed, RoundingPolicy::Unrounded).unwrap(), Money::parse("2 USD").unwrap());
+
+        // Squares that do not fit a Decimal are reported rather than panicking
+        let wide = [Money::from_major(1_000_000_000_000_000, USD), Money::from_major(-1_000_000_000_000_000, USD)];
+        assert_eq!(std_dev(&wide, CENTS), Err(MoneyError::Overflow));
+        let wide: Vec<Money<crate::Decimal256>> =
+            wide.iter().map(|money| Money::parse(&format!("{} USD", money.amount())).unwrap()).collect();
+        assert_eq!(std_dev(&wide, CENTS).unwrap(), Money::parse("1000000000000000 USD").unwrap());
+    }
+
+    #[test]
+    fn stats_errors() {
+        let empty: Vec<Money> = Vec::new();
+        assert_eq!(mean(&empty, CENTS), Err(MoneyError::EmptySum));
+        assert_eq!(median(&empty, CENTS), Err(MoneyError::EmptySum));
+        assert_eq!(min(&empty), Err(MoneyError::EmptySum));
+        assert_eq!(std_dev(&empty, CENTS), Err(MoneyError::EmptySum));
+
+        let mixed = vec![Money::from_minor(100, USD), Money::from_minor(100, EUR)];
+        assert_eq!(mean(&mixed, CENTS), Err(MoneyError::CurrencyMismatch));
+        assert_eq!(max(&mixed), Err(MoneyError::CurrencyMismatch));
+        assert_eq!(
+            weighted_mean(mixed.iter().map(|m| (m, dec!(1))), CENTS),
+            Err(MoneyError::CurrencyMismatch)
+        );
+
+        let huge = vec![Money::from_decimal(Decimal::MAX, USD), Money::from_decimal(Decimal::MAX, USD)];
+        assert_eq!(mean(&huge, CENTS), Err(MoneyError::Overflow));
+        assert_eq!(weighted_mean(huge.iter().map(|m| (m, dec!(1))), CENTS), Err(MoneyError::Overflow));
+        let heavy = [(Money::from_minor(100, USD), Decimal::MAX), (Money::from_minor(100, USD), Decimal::MAX)];
+        assert_eq!(weighted_mean(heavy.iter().map(|(m, w)| (m, *w)), CENTS), Err(MoneyError::Overflow));
+    }
+}
@@ -0,0 +1,443 @@
+use crate::{Amount, Currency, Money, MoneyError};
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+
+/// A named tax rate such as a VAT, GST or state sales tax.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaxRate {
+    pub name: String,
+    /// Rate as a decimal, e.g. `0.19` for 19%.
+    pub rate: Decimal,
+    /// Compound taxes are charged on the net amount plus every tax listed before them.
+    #[serde(default)]
+    pub compound: bool,
+}
+
+impl TaxRate {
+    /// Creates a tax charged on the net amount.
+    pub fn new(name: &str, rate: Decimal) -> TaxRate {
+        TaxRate {
+            name: name.to_string(),
+            rate,
+            compound: false,
+        }
+    }
+
+    /// Creates a tax charged on the net amount plus the taxes listed before it, like Quebec's QST before 2013.
+    pub fn compound(name: &str, rate: Decimal) -> TaxRate {
+        TaxRate {
+            name: name.to_string(),
+            rate,
+            compound: true,
+        }
+    }
+}
+
+/// Where taxes on several lines are rounded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaxRounding {
+    /// Every line's taxes are rounded, and the invoice total is the sum of the rounded lines.
+    PerLine,
+    /// Taxes are computed and rounded once on the invoice total, then allocated back to the lines.
+    PerInvoice,
+}
+
+/// The tax charged by one rate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaxLine<A = Decimal> {
+    pub name: String,
+    pub rate: Decimal,
+    /// Amount the rate was applied to.
+    pub base: Money<A>,
+    pub amount: Money<A>,
+}
+
+/// A net amount, the taxes on it and the resulting gross amount.
+///
+/// The net amount plus every tax always equals the gross amount exactly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaxBreakdown<A = Decimal> {
+    pub net: Money<A>,
+    pub taxes: Vec<TaxLine<A>>,
+    pub gross: Money<A>,
+}
+
+impl<A: Amount> TaxBreakdown<A> {
+    /// Returns the sum of all taxes.
+    pub fn total_tax(&self) -> Money<A> {
+        let total = self.taxes.iter().fold(A::zero(), |acc, tax| acc + tax.amount.amount().clone());
+        Money::from_amount(total, *self.net.currency())
+    }
+}
+
+/// Tax breakdowns for each line of an invoice and for the invoice as a whole.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaxedLines<A = Decimal> {
+    pub lines: Vec<TaxBreakdown<A>>,
+    pub total: TaxBreakdown<A>,
+}
+
+/// A set of tax rates with the rules for rounding them.
+///
+/// Amounts are rounded to the currency's minor unit with `strategy`; use `MidpointAwayFromZero` for half-up
+/// rounding and `MidpointNearestEven` for banker's rounding.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_money::{Money, TaxRate, TaxRounding, TaxScheme};
+/// use rusty_money::currencies::iso::CAD;
+/// use rust_decimal::RoundingStrategy;
+/// use rust_decimal_macros::dec;
+///
+/// let quebec = TaxScheme::new(
+///     vec![TaxRate::new("GST", dec!(0.05)), TaxRate::new("QST", dec!(0.09975))],
+///     TaxRounding::PerLine,
+///     RoundingStrategy::MidpointAwayFromZero,
+/// );
+///
+/// let sale = quebec.add_tax(&Money::from_major(100, CAD));
+/// assert_eq!(sale.taxes[0].amount, Money::from_minor(500, CAD));
+/// assert_eq!(sale.taxes[1].amount, Money::from_minor(998, CAD));
+/// assert_eq!(sale.gross, Money::from_minor(11_498, CAD));
+///
+/// let receipt = quebec.extract_tax(&Money::from_minor(11_498, CAD)).unwrap();
+/// assert_eq!(receipt.net, Money::from_major(100, CAD));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaxScheme {
+    pub rates: Vec<TaxRate>,
+    pub rounding: TaxRounding,
+    pub strategy: RoundingStrategy,
+}
+
+impl TaxScheme {
+    pub fn new(rates: Vec<TaxRate>, rounding: TaxRounding, strategy: RoundingStrategy) -> TaxScheme {
+        TaxScheme {
+            rates,
+            rounding,
+            strategy,
+        }
+    }
+
+    /// Adds taxes to a net (tax exclusive) price.
+    ///
+    /// The net amount is rounded to the currency first, then each tax is rounded on its own.
+    pub fn add_tax<A: Amount>(&self, net: &Money<A>) -> TaxBreakdown<A> {
+        let net = self.round(net.clone());
+        let taxes = self.taxes_on(&net);
+        let gross = Money::from_amount(net.amount().clone() + sum_taxes(&taxes), *net.currency());
+        TaxBreakdown { net, taxes, gross }
+    }
+
+    /// Extracts the taxes contained in a gross (tax inclusive) price.
+    ///
+    /// Each tax is computed on the exact net amount and rounded, and the net amount is whatever remains of the
+    /// rounded gross amount, so the breakdown always adds back up to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::InvalidRatio` if the rates add up to -100%, which leaves no net amount to solve for.
+    pub fn extract_tax<A: Amount>(&self, gross: &Money<A>) -> Result<TaxBreakdown<A>, MoneyError> {
+        let gross = self.round(gross.clone());
+        let factor = Decimal::ONE
+            .checked_div(self.gross_factor())
+            .ok_or(MoneyError::InvalidRatio)?;
+        let exact_net = Money::from_amount(gross.amount().mul_decimal(factor), *gross.currency());
+        let taxes = self.taxes_on(&exact_net);
+        let net = Money::from_amount(gross.amount().clone() - sum_taxes(&taxes), *gross.currency());
+        Ok(TaxBreakdown { net, taxes, gross })
+    }
+
+    /// Adds taxes to the net prices of several invoice lines, rounding as configured by `rounding`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_money::{Money, TaxRate, TaxRounding, TaxScheme};
+    /// use rusty_money::currencies::iso::EUR;
+    /// use rust_decimal::RoundingStrategy;
+    /// use rust_decimal_macros::dec;
+    ///
+    /// let lines = vec![Money::from_minor(99, EUR); 3];
+    /// let mut vat = TaxScheme::new(
+    ///     vec![TaxRate::new("VAT", dec!(0.19))],
+    ///     TaxRounding::PerLine,
+    ///     RoundingStrategy::MidpointAwayFromZero,
+    /// );
+    /// assert_eq!(vat.add_tax_to_lines(&lines).unwrap().total.total_tax(), Money::from_minor(57, EUR));
+    ///
+    /// vat.rounding = TaxRounding::PerInvoice;
+    /// assert_eq!(vat.add_tax_to_lines(&lines).unwrap().total.total_tax(), Money::from_minor(56, EUR));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::EmptySum` without lines and `MoneyError::CurrencyMismatch` for mixed currencies. With
+    /// `TaxRounding::PerInvoice`, taxes are allocated in proportion to the lines, which returns
+    /// `MoneyError::InvalidRatio` if a line is negative or all of them are zero.
+    pub fn add_tax_to_lines<A: Amount>(&self, nets: &[Money<A>]) -> Result<TaxedLines<A>, MoneyError> {
+        check_lines(nets)?;
+        match self.rounding {
+            TaxRounding::PerLine => sum_lines(nets.iter().map(|net| self.add_tax(net)).collect()),
+            TaxRounding::PerInvoice => {
+                let nets: Vec<Money<A>> = nets.iter().map(|net| self.round(net.clone())).collect();
+                let total = self.add_tax(&sum_money(&nets));
+                self.allocate_total(total, &nets, |net, taxes| {
+                    let gross = net.amount().clone() + sum_taxes(&taxes);
+                    (net.clone(), Money::from_amount(gross, *net.currency()), taxes)
+                })
+            }
+        }
+    }
+
+    /// Extracts the taxes contained in the gross prices of several invoice lines, rounding as configured by
+    /// `rounding`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::EmptySum` without lines and `MoneyError::CurrencyMismatch` for mixed currencies. With
+    /// `TaxRounding::PerInvoice`, taxes are allocated in proportion to the lines, which returns
+    /// `MoneyError::InvalidRatio` if a line is negative or all of them are zero.
+    pub fn extract_tax_from_lines<A: Amount>(&self, grosses: &[Money<A>]) -> Result<TaxedLines<A>, MoneyError> {
+        check_lines(grosses)?;
+        match self.rounding {
+            TaxRounding::PerLine => {
+                sum_lines(grosses.iter().map(|gross| self.extract_tax(gross)).collect::<Result<_, _>>()?)
+            }
+            TaxRounding::PerInvoice => {
+                let grosses: Vec<Money<A>> = grosses.iter().map(|gross| self.round(gross.clone())).collect();
+                let total = self.extract_tax(&sum_money(&grosses))?;
+                self.allocate_total(total, &grosses, |gross, taxes| {
+                    let net = gross.amount().clone() - sum_taxes(&taxes);
+                    (Money::from_amount(net, *gross.currency()), gross.clone(), taxes)
+                })
+            }
+        }
+    }
+
+    fn round<A: Amount>(&self, money: Money<A>) -> Money<A> {
+        let exponent = money.currency().exponent;
+        money.round(exponent, self.strategy)
+    }
+
+    /// Computes and rounds each tax on a net amount, which may itself be unrounded.
+    fn taxes_on<A: Amount>(&self, net: &Money<A>) -> Vec<TaxLine<A>> {
+        let mut taxes: Vec<TaxLine<A>> = Vec::with_capacity(self.rates.len());
+        for rate in &self.rates {
+            let mut base = net.amount().clone();
+            if rate.compound {
+                base = base + sum_taxes(&taxes);
+            }
+            let base = Money::from_amount(base, *net.currency());
+            let amount = self.round(Money::from_amount(base.amount().mul_decimal(rate.rate), *net.currency()));
+            taxes.push(TaxLine {
+                name: rate.name.clone(),
+                rate: rate.rate,
+                base: self.round(base),
+                amount,
+            });
+        }
+        taxes
+    }
+
+    /// Gross amount per unit of net amount, before any rounding.
+    fn gross_factor(&self) -> Decimal {
+        let mut taxes = Decimal::ZERO;
+        for rate in &self.rates {
+            let base = if rate.compound { Decimal::ONE + taxes } else { Decimal::ONE };
+            taxes += base * rate.rate;
+        }
+        Decimal::ONE + taxes
+    }
+
+    /// Splits the taxes of an invoice total over its lines in proportion to `weights`, one line per weight.
+    fn allocate_total<A: Amount>(
+        &self,
+        total: TaxBreakdown<A>,
+        weights: &[Money<A>],
+        build: impl Fn(&Money<A>, Vec<TaxLine<A>>) -> (Money<A>, Money<A>, Vec<TaxLine<A>>),
+    ) -> Result<TaxedLines<A>, MoneyError> {
+        let weights: Vec<A> = weights.iter().map(|w| w.amount().clone()).collect();
+        let mut shares = Vec::with_capacity(total.taxes.len());
+        for tax in &total.taxes {
+            shares.push((tax.base.allocate_weighted(&weights)?, tax.amount.allocate_weighted(&weights)?));
+        }
+
+        let mut lines = Vec::with_capacity(weights.len());
+        for (index, line) in weights.iter().enumerate() {
+            let taxes = total
+                .taxes
+                .iter()
+                .zip(&shares)
+                .map(|(tax, (bases, amounts))| TaxLine {
+                    name: tax.name.clone(),
+                    rate: tax.rate,
+                    base: bases[index].clone(),
+                    amount: amounts[index].clone(),
+                })
+                .collect();
+            let (net, gross, taxes) = build(&Money::from_amount(line.clone(), *total.net.currency()), taxes);
+            lines.push(TaxBreakdown { net, taxes, gross });
+        }
+        Ok(TaxedLines { lines, total })
+    }
+}
+
+fn sum_taxes<A: Amount>(taxes: &[TaxLine<A>]) -> A {
+    taxes.iter().fold(A::zero(), |acc, tax| acc + tax.amount.amount().clone())
+}
+
+fn sum_money<A: Amount>(monies: &[Money<A>]) -> Money<A> {
+    let total = monies.iter().fold(A::zero(), |acc, money| acc + money.amount().clone());
+    Money::from_amount(total, *monies[0].currency())
+}
+
+fn check_lines<A: Amount>(lines: &[Money<A>]) -> Result<Currency, MoneyError> {
+    let currency = *lines.first().ok_or(MoneyError::EmptySum)?.currency();
+    if lines.iter().any(|line| *line.currency() != currency) {
+        return Err(MoneyError::CurrencyMismatch);
+    }
+    Ok(currency)
+}
+
+/// Adds up line breakdowns into an invoice total.
+fn sum_lines<A: Amount>(lines: Vec<TaxBreakdown<A>>) -> Result<TaxedLines<A>, MoneyError> {
+    let mut total = lines[0].clone();
+    for line in &lines[1..] {
+        total.net = (total.net + line.net.clone())?;
+        total.gross = (total.gross + line.gross.clone())?;
+        for (sum, tax) in total.taxes.iter_mut().zip(&line.taxes) {
+            sum.base = (sum.base.clone() + tax.base.clone())?;
+            sum.amount = (sum.amount.clone() + tax.amount.clone())?;
+        }
+    }
+    Ok(TaxedLines { lines, total })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::currencies::iso::{CAD, EUR, USD};
+    use crate::FixedPoint;
+    use rust_decimal_macros::dec;
+
+    fn scheme(rates: Vec<TaxRate>, rounding: TaxRounding) -> TaxScheme {
+        TaxScheme::new(rates, rounding, RoundingStrategy::MidpointAwayFromZero)
+    }
+
+    fn assert_reconciles<A: Amount>(breakdown: &TaxBreakdown<A>) {
+        assert_eq!((breakdown.net.clone() + breakdown.total_tax()).unwrap(), breakdown.gross);
+    }
+
+    #[test]
+    fn tax_exclusive_pricing() {
+        let vat = scheme(vec![TaxRate::new("VAT", dec!(0.10))], TaxRounding::PerLine);
+        let breakdown = vat.add_tax(&Money::from_minor(1_025, EUR));
+        assert_eq!(breakdown.taxes[0].amount, Money::from_minor(103, EUR));
+        assert_eq!(breakdown.gross, Money::from_minor(1_128, EUR));
+
+        let bankers = TaxScheme { strategy: RoundingStrategy::MidpointNearestEven, ..vat };
+        let breakdown = bankers.add_tax(&Money::from_minor(1_025, EUR));
+        assert_eq!(breakdown.taxes[0].amount, Money::from_minor(102, EUR));
+        assert_reconciles(&breakdown);
+
+        // Sub-cent net prices are rounded before taxing
+        let breakdown = bankers.add_tax(&Money::from_decimal(dec!(10.005), EUR));
+        assert_eq!(breakdown.net, Money::from_minor(1_000, EUR));
+    }
+
+    #[test]
+    fn tax_inclusive_pricing() {
+        let vat = scheme(vec![TaxRate::new("VAT", dec!(0.19))], TaxRounding::PerLine);
+        let breakdown = vat.extract_tax(&Money::from_minor(11_900, EUR)).unwrap();
+        assert_eq!(breakdown.net, Money::from_minor(10_000, EUR));
+        assert_eq!(breakdown.taxes[0].amount, Money::from_minor(1_900, EUR));
+
+        let breakdown = vat.extract_tax(&Money::from_minor(1_000, EUR)).unwrap();
+        assert_eq!(breakdown.taxes[0].amount, Money::from_minor(160, EUR));
+        assert_eq!(breakdown.net, Money::from_minor(840, EUR));
+        assert_reconciles(&breakdown);
+
+        // Without rates the net amount is the gross amount
+        let none = scheme(vec![], TaxRounding::PerLine);
+        assert_eq!(none.extract_tax(&Money::from_minor(999, EUR)).unwrap().net, Money::from_minor(999, EUR));
+    }
+
+    #[test]
+    fn tax_compound_rates() {
+        let quebec_2012 = scheme(
+            vec![TaxRate::new("GST", dec!(0.05)), TaxRate::compound("QST", dec!(0.085))],
+            TaxRounding::PerLine,
+        );
+        let sale = quebec_2012.add_tax(&Money::from_major(100, CAD));
+        assert_eq!(sale.taxes[1].base, Money::from_major(105, CAD));
+        assert_eq!(sale.taxes[1].amount, Money::from_minor(893, CAD));
+        assert_eq!(sale.gross, Money::from_minor(11_393, CAD));
+
+        let receipt = quebec_2012.extract_tax(&sale.gross).unwrap();
+        assert_eq!(receipt, sale);
+    }
+
+    #[test]
+    fn tax_lines_exclusive() {
+        let lines = vec![Money::from_minor(99, EUR); 3];
+        let per_line = scheme(vec![TaxRate::new("VAT", dec!(0.19))], TaxRounding::PerLine);
+        let taxed = per_line.add_tax_to_lines(&lines).unwrap();
+        assert_eq!(taxed.total.taxes[0].amount, Money::from_minor(57, EUR));
+        assert_eq!(taxed.total.gross, Money::from_minor(354, EUR));
+
+        let per_invoice = TaxScheme { rounding: TaxRounding::PerInvoice, ..per_line };
+        let taxed = per_invoice.add_tax_to_lines(&lines).unwrap();
+        assert_eq!(taxed.total.taxes[0].amount, Money::from_minor(56, EUR));
+        let line_taxes: Vec<Money> = taxed.lines.iter().map(|line| line.total_tax()).collect();
+        let expected = vec![Money::from_minor(19, EUR), Money::from_minor(19, EUR), Money::from_minor(18, EUR)];
+        assert_eq!(line_taxes, expected);
+
+        let gross_total = taxed.lines.iter().fold(Money::from_minor(0, EUR), |acc, l| (acc + l.gross.clone()).unwrap());
+        assert_eq!(gross_total, taxed.total.gross);
+        taxed.lines.iter().for_each(assert_reconciles);
+    }
+
+    #[test]
+    fn tax_lines_inclusive() {
+        let grosses = vec![Money::from_minor(1_000, EUR), Money::from_minor(1_000, EUR), Money::from_minor(1_000, EUR)];
+        let per_line = scheme(vec![TaxRate::new("VAT", dec!(0.19))], TaxRounding::PerLine);
+        let taxed = per_line.extract_tax_from_lines(&grosses).unwrap();
+        assert_eq!(taxed.total.total_tax(), Money::from_minor(480, EUR));
+
+        let per_invoice = TaxScheme { rounding: TaxRounding::PerInvoice, ..per_line };
+        let taxed = per_invoice.extract_tax_from_lines(&grosses).unwrap();
+        assert_eq!(taxed.total.total_tax(), Money::from_minor(479, EUR));
+        assert_eq!(taxed.total.net, Money::from_minor(2_521, EUR));
+        taxed.lines.iter().for_each(assert_reconciles);
+        let nets: Vec<Money> = taxed.lines.iter().map(|line| line.net.clone()).collect();
+        assert_eq!(nets, vec![Money::from_minor(840, EUR), Money::from_minor(840, EUR), Money::from_minor(841, EUR)]);
+    }
+
+    #[test]
+    fn tax_lines_errors() {
+        let vat = scheme(vec![TaxRate::new("VAT", dec!(0.19))], TaxRounding::PerInvoice);
+        let empty: Vec<Money> = vec![];
+        assert_eq!(vat.add_tax_to_lines(&empty), Err(MoneyError::EmptySum));
+        let mixed = vec![Money::from_minor(100, EUR), Money::from_minor(100, USD)];
+        assert_eq!(vat.extract_tax_from_lines(&mixed), Err(MoneyError::CurrencyMismatch));
+        let refund = vec![Money::from_minor(100, EUR), Money::from_minor(-50, EUR)];
+        assert_eq!(vat.add_tax_to_lines(&refund), Err(MoneyError::InvalidRatio));
+    }
+
+    #[test]
+    fn tax_other_backends() {
+        let vat = scheme(vec![TaxRate::new("VAT", dec!(0.075))], TaxRounding::PerLine);
+        let breakdown = vat.extract_tax(&Money::<FixedPoint<4>>::parse("21.50 USD").unwrap()).unwrap();
+        assert_eq!(breakdown.taxes[0].amount, Money::parse("1.50 USD").unwrap());
+        assert_eq!(breakdown.net, Money::parse("20.00 USD").unwrap());
+    }
+
+    #[test]
+    fn tax_rate_deserializes() {
+        let json = r#"[{"name": "GST", "rate": "0.05"}, {"name": "QST", "rate": "0.085", "compound": true}]"#;
+        let rates: Vec<TaxRate> = serde_json::from_str(json).unwrap();
+        assert_eq!(rates, vec![TaxRate::new("GST", dec!(0.05)), TaxRate::compound("QST", dec!(0.085))]);
+    }
+}